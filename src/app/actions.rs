@@ -1,36 +1,110 @@
-use std::io::{self, Stdout};
+use std::io::{self, Stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::Result;
 use crossterm::{
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
+use serde::Deserialize;
+
+/// When to hold command output on screen before returning to the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PauseAfter {
+    Always,
+    #[default]
+    OnError,
+    Never,
+}
+
+impl PauseAfter {
+    fn applies(&self, failed: bool) -> bool {
+        match self {
+            PauseAfter::Always => true,
+            PauseAfter::OnError => failed,
+            PauseAfter::Never => false,
+        }
+    }
+}
+
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Turns mouse reporting on or off, remembering it so a suspended TUI comes back the same.
+pub fn set_mouse_capture(enabled: bool) -> io::Result<()> {
+    MOUSE_CAPTURE.store(enabled, Ordering::Relaxed);
+    if enabled {
+        execute!(io::stdout(), EnableMouseCapture)
+    } else {
+        execute!(io::stdout(), DisableMouseCapture)
+    }
+}
 
 pub fn suspend_tui_for_command(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     msg: &str,
+    pause: PauseAfter,
     f: impl FnOnce() -> Result<()>,
 ) -> Result<()> {
     show_transient_message(terminal, msg)?;
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    // A password prompt would otherwise receive pastes wrapped in escape sequences.
+    execute!(
+        io::stdout(),
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
 
     let run_result = f();
 
-    execute!(io::stdout(), EnterAlternateScreen)?;
+    if pause.applies(run_result.is_err()) {
+        wait_for_keypress()?;
+    }
+    execute!(io::stdout(), EnterAlternateScreen, EnableBracketedPaste)?;
+    if MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    }
     enable_raw_mode()?;
+    terminal.clear()?;
 
     run_result
 }
 
+fn wait_for_keypress() -> Result<()> {
+    print!("\nPress any key to return to TUIHub");
+    io::stdout().flush()?;
+    enable_raw_mode()?;
+    // Drop keys typed while the command ran so they don't skip the pause.
+    while event::poll(std::time::Duration::ZERO)? {
+        event::read()?;
+    }
+    let waited = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => break Ok(()),
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        }
+    };
+    disable_raw_mode()?;
+    println!();
+    waited?;
+    Ok(())
+}
+
 fn show_transient_message(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     msg: &str,
 ) -> Result<()> {
+    use crate::ui::glyphs::glyphs;
     use crate::ui::layout::centered_rect;
     use ratatui::style::{Color, Style};
-    use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap};
+    use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
     terminal.draw(|frame| {
         let area = centered_rect(70, 20, frame.area());
@@ -42,7 +116,7 @@ fn show_transient_message(
                 Block::default()
                     .title(" Running External Command ")
                     .borders(Borders::ALL)
-                    .border_type(BorderType::Thick),
+                    .border_set(glyphs().thick_border),
             );
         frame.render_widget(block, area);
     })?;
@@ -1,8 +1,9 @@
 pub mod actions;
+pub mod outcome;
+pub mod persist;
+pub mod queue;
 pub mod state;
 pub mod update;
 
 pub use state::App;
-pub use update::{
-    refresh_filter, run,
-};
+pub use update::{refresh_filter, run};
@@ -0,0 +1,160 @@
+use crate::registry::model::AppEntry;
+use crate::system::exec::{entry_command, InstallState};
+use crate::system::os::Platform;
+
+/// What an install, uninstall or launch should do with one entry. Decided from the cached
+/// install state without touching the terminal, so the event loop only has to act on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionOutcome {
+    UnknownPlatform,
+    AlreadyInstalled,
+    NotInstalled,
+    /// The binary on PATH belongs to some other program.
+    Conflict,
+    /// The catalog has no command for this platform.
+    NoCommand,
+    /// Good to go: run this command.
+    Run(String),
+}
+
+/// A conflicting binary doesn't count as installed, so installing over it is allowed.
+pub fn decide_install(entry: &AppEntry, platform: Platform, state: InstallState) -> ActionOutcome {
+    if platform == Platform::Unknown {
+        return ActionOutcome::UnknownPlatform;
+    }
+    if state == InstallState::Installed {
+        return ActionOutcome::AlreadyInstalled;
+    }
+    match entry_command(entry, &entry.install, platform) {
+        Some(cmd) => ActionOutcome::Run(cmd),
+        None => ActionOutcome::NoCommand,
+    }
+}
+
+pub fn decide_uninstall(
+    entry: &AppEntry,
+    platform: Platform,
+    state: InstallState,
+) -> ActionOutcome {
+    if platform == Platform::Unknown {
+        return ActionOutcome::UnknownPlatform;
+    }
+    if state != InstallState::Installed {
+        return ActionOutcome::NotInstalled;
+    }
+    match entry_command(entry, &entry.uninstall, platform) {
+        Some(cmd) => ActionOutcome::Run(cmd),
+        None => ActionOutcome::NoCommand,
+    }
+}
+
+/// Launching needs the real program, so a conflict is refused rather than run.
+pub fn decide_launch(entry: &AppEntry, state: InstallState) -> ActionOutcome {
+    match state {
+        InstallState::Conflict => ActionOutcome::Conflict,
+        InstallState::Missing => ActionOutcome::NotInstalled,
+        InstallState::Installed => ActionOutcome::Run(entry.launch_command().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::model::test_entry;
+
+    /// Installs with `brew` on macOS and has no Windows command.
+    fn entry() -> AppEntry {
+        test_entry(
+            "fd",
+            serde_json::json!({
+                "binary": "fd",
+                "launch_command": "fd --help",
+                "install": {
+                    "linux": "apt install fd-find",
+                    "wsl": "apt install fd-find",
+                    "mac": "brew install fd",
+                    "windows": ""
+                },
+                "uninstall": {
+                    "linux": "apt remove fd-find",
+                    "wsl": "apt remove fd-find",
+                    "mac": "brew uninstall fd",
+                    "windows": ""
+                }
+            }),
+        )
+    }
+
+    fn run(cmd: &str) -> ActionOutcome {
+        ActionOutcome::Run(cmd.to_string())
+    }
+
+    #[test]
+    fn install() {
+        let entry = entry();
+        let decide = |platform, state| decide_install(&entry, platform, state);
+        assert_eq!(
+            decide(Platform::Unknown, InstallState::Missing),
+            ActionOutcome::UnknownPlatform
+        );
+        assert_eq!(
+            decide(Platform::Mac, InstallState::Installed),
+            ActionOutcome::AlreadyInstalled
+        );
+        assert_eq!(
+            decide(Platform::Windows, InstallState::Missing),
+            ActionOutcome::NoCommand
+        );
+        assert_eq!(
+            decide(Platform::Mac, InstallState::Missing),
+            run("brew install fd")
+        );
+        assert_eq!(
+            decide(Platform::Mac, InstallState::Conflict),
+            run("brew install fd")
+        );
+    }
+
+    #[test]
+    fn uninstall() {
+        let entry = entry();
+        let decide = |platform, state| decide_uninstall(&entry, platform, state);
+        assert_eq!(
+            decide(Platform::Unknown, InstallState::Installed),
+            ActionOutcome::UnknownPlatform
+        );
+        assert_eq!(
+            decide(Platform::Mac, InstallState::Missing),
+            ActionOutcome::NotInstalled
+        );
+        assert_eq!(
+            decide(Platform::Mac, InstallState::Conflict),
+            ActionOutcome::NotInstalled
+        );
+        assert_eq!(
+            decide(Platform::Windows, InstallState::Installed),
+            ActionOutcome::NoCommand
+        );
+        assert_eq!(
+            decide(Platform::Mac, InstallState::Installed),
+            run("brew uninstall fd")
+        );
+    }
+
+    #[test]
+    fn launch() {
+        let entry = entry();
+        assert_eq!(
+            decide_launch(&entry, InstallState::Conflict),
+            ActionOutcome::Conflict
+        );
+        assert_eq!(
+            decide_launch(&entry, InstallState::Missing),
+            ActionOutcome::NotInstalled
+        );
+        assert_eq!(
+            decide_launch(&entry, InstallState::Installed),
+            run("fd --help")
+        );
+    }
+}
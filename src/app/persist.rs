@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::state::{DetailSection, SortMode};
+use crate::system::paths::state_dir;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersistedState {
+    pub selected_ids: Vec<String>,
+    pub favorites: Vec<String>,
+    /// Catalog ids seen in earlier runs; `None` until the first save.
+    pub known_ids: Option<Vec<String>>,
+    /// App id to the unix timestamp of its last successful launch.
+    pub last_launched: BTreeMap<String, i64>,
+    pub launch_counts: BTreeMap<String, u32>,
+    pub sort_mode: SortMode,
+    pub selected_tab: usize,
+    pub category: Option<String>,
+    pub search: String,
+    /// Newest first.
+    pub search_history: Vec<String>,
+    pub collapsed_sections: Vec<DetailSection>,
+}
+
+impl PersistedState {
+    pub fn path() -> Option<PathBuf> {
+        state_dir().map(|dir| dir.join("state.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        let raw = serde_json::to_string_pretty(self)?;
+        fs::write(&path, raw).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::registry::model::AppEntry;
+use crate::system::exec::{
+    install_retries, install_timeout, run_install_in_background, CommandCancelled,
+};
+use crate::system::os::Platform;
+
+/// An install or uninstall handed to the background worker, with everything resolved up
+/// front so the worker never touches `App`.
+pub struct InstallJob {
+    pub entry: AppEntry,
+    pub kind: JobKind,
+    pub cmd: String,
+    /// Only for installs.
+    pub post_cmd: Option<String>,
+    pub env: Vec<(String, String)>,
+    pub platform: Platform,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Install,
+    Uninstall,
+}
+
+impl JobKind {
+    fn verb(self) -> &'static str {
+        match self {
+            JobKind::Install => "installing",
+            JobKind::Uninstall => "uninstalling",
+        }
+    }
+}
+
+pub enum QueueEvent {
+    Started(JobKind, String),
+    Finished {
+        entry: Box<AppEntry>,
+        kind: JobKind,
+        result: Result<()>,
+        post_result: Option<Result<()>>,
+        retry_notes: Vec<String>,
+        took: Duration,
+    },
+}
+
+/// Installs that run one at a time on a worker thread while the TUI keeps taking keys: the
+/// ones queued with `Shift+I`, and every install or uninstall of an `interactive: false`
+/// entry.
+/// Commands run with stdin closed and their output captured, so anything that prompts fails
+/// instead of hanging. The worker has its own cancel token, which [`InstallQueue::cancel`]
+/// sets; a Ctrl-C meant for a command run from the event loop leaves it alone.
+#[derive(Default)]
+pub struct InstallQueue {
+    /// Started on the first push.
+    worker: Option<Worker>,
+    /// Ids of queued jobs, oldest first, including the one running.
+    pending: VecDeque<String>,
+    running: Option<(JobKind, String)>,
+}
+
+impl InstallQueue {
+    pub fn push(&mut self, job: InstallJob) {
+        let worker = self.worker.get_or_insert_with(spawn_worker);
+        self.pending.push_back(job.entry.id.clone());
+        let generation = worker.generation.load(Ordering::SeqCst);
+        // The worker only stops when the queue is dropped, so sending can't fail.
+        if let Some(jobs) = &worker.jobs {
+            let _ = jobs.send((generation, job));
+        }
+    }
+
+    /// Stops the running job, process group and all, and skips everything queued behind it.
+    /// Jobs pushed afterwards run normally. Returns false when there was nothing to cancel.
+    pub fn cancel(&mut self) -> bool {
+        match &self.worker {
+            Some(worker) if self.is_busy() => {
+                worker.cancel_all();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.pending.iter().any(|queued| queued == id)
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Events the worker sent since the last call.
+    pub fn poll(&mut self) -> Vec<QueueEvent> {
+        let Some(worker) = &self.worker else {
+            return Vec::new();
+        };
+        let events: Vec<QueueEvent> = worker.events.try_iter().collect();
+        for event in &events {
+            match event {
+                QueueEvent::Started(kind, name) => self.running = Some((*kind, name.clone())),
+                QueueEvent::Finished { entry, .. } => {
+                    self.running = None;
+                    if let Some(pos) = self.pending.iter().position(|id| *id == entry.id) {
+                        self.pending.remove(pos);
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    /// Footer text such as `queue: 3 pending, installing fzf`.
+    pub fn summary(&self) -> Option<String> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let waiting = self.pending.len() - usize::from(self.running.is_some());
+        Some(match &self.running {
+            Some((kind, name)) => format!("queue: {waiting} pending, {} {name}", kind.verb()),
+            None => format!("queue: {waiting} pending"),
+        })
+    }
+}
+
+struct Worker {
+    /// Taken on drop, which ends the worker's job loop.
+    jobs: Option<Sender<(u64, InstallJob)>>,
+    events: Receiver<QueueEvent>,
+    /// Kills the running command; consumed by it.
+    cancel: Arc<AtomicBool>,
+    /// Bumped by a cancel. Jobs pushed under an older generation are skipped.
+    generation: Arc<AtomicU64>,
+    /// Keeps the worker from starting anything else.
+    stopping: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Quitting with jobs queued stops the running one, process group and all, rather than
+/// leaving it to finish unwatched.
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        self.cancel_all();
+        self.jobs = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Worker {
+    /// The generation goes up before the token is set, so the worker either sees the new
+    /// generation and skips the job, or starts it and gets killed by the token.
+    fn cancel_all(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+}
+
+fn spawn_worker() -> Worker {
+    let (job_tx, job_rx) = mpsc::channel::<(u64, InstallJob)>();
+    let (event_tx, event_rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let generation = Arc::new(AtomicU64::new(0));
+    let stopping = Arc::new(AtomicBool::new(false));
+    let (token, current, stop) = (
+        Arc::clone(&cancel),
+        Arc::clone(&generation),
+        Arc::clone(&stopping),
+    );
+    let thread = thread::spawn(move || {
+        for (pushed_under, job) in job_rx {
+            if stop.load(Ordering::SeqCst) {
+                return;
+            }
+            // A cancel that found nothing running leaves the token set; it was meant for
+            // the jobs it skips, not for this one.
+            token.store(false, Ordering::SeqCst);
+            if pushed_under != current.load(Ordering::SeqCst) {
+                let skipped = QueueEvent::Finished {
+                    entry: Box::new(job.entry),
+                    kind: job.kind,
+                    result: Err(CommandCancelled.into()),
+                    post_result: None,
+                    retry_notes: Vec::new(),
+                    took: Duration::ZERO,
+                };
+                if event_tx.send(skipped).is_err() {
+                    return;
+                }
+                continue;
+            }
+            if event_tx
+                .send(QueueEvent::Started(job.kind, job.entry.name.clone()))
+                .is_err()
+            {
+                return;
+            }
+            let started = Instant::now();
+            let mut retry_notes = Vec::new();
+            let result = run_install_in_background(
+                &job.cmd,
+                job.platform,
+                install_timeout(),
+                &job.env,
+                match job.kind {
+                    JobKind::Install => install_retries(&job.entry),
+                    JobKind::Uninstall => 0,
+                },
+                &token,
+                |note| retry_notes.push(note),
+            );
+            let post_result = match (&result, &job.post_cmd) {
+                (Ok(()), Some(cmd)) => Some(run_install_in_background(
+                    cmd,
+                    job.platform,
+                    install_timeout(),
+                    &job.env,
+                    0,
+                    &token,
+                    |_| {},
+                )),
+                _ => None,
+            };
+            let finished = QueueEvent::Finished {
+                entry: Box::new(job.entry),
+                kind: job.kind,
+                result,
+                post_result,
+                retry_notes,
+                took: started.elapsed(),
+            };
+            if event_tx.send(finished).is_err() {
+                return;
+            }
+        }
+    });
+    Worker {
+        jobs: Some(job_tx),
+        events: event_rx,
+        cancel,
+        generation,
+        stopping,
+        thread: Some(thread),
+    }
+}
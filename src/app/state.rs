@@ -1,14 +1,193 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
 
+use chrono::{DateTime, Utc};
+use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
 
+use super::actions::PauseAfter;
+use super::persist::PersistedState;
+use super::queue::InstallQueue;
 use crate::registry::model::AppEntry;
-use crate::system::exec::is_binary_installed;
+use crate::system::exec::{
+    command_for_platform, detect_install_state_cached, failure_output, outdated_version,
+    InstallState, VerifyCache,
+};
 use crate::system::os::Platform;
+use crate::system::tmux::TmuxTarget;
+use crate::system::watch::CatalogWatcher;
+use crate::ui::theme::{Theme, ThemePreset};
+use crate::utils::{SearchField, SearchQuery};
 
+const SEARCH_HISTORY_LIMIT: usize = 20;
+
+pub const TABS: [&str; 4] = ["All", "Installed", "Categories", "Outdated"];
+
+/// What runs when a confirm dialog is accepted.
 #[derive(Clone)]
 pub enum ConfirmAction {
+    Install(Vec<AppEntry>),
     Uninstall(Vec<AppEntry>),
+    /// Launch again although the app already runs at the given tmux `session:window`.
+    Launch(Box<AppEntry>, String),
+    /// A batch launch above `LaunchLimits::confirm_above`.
+    LaunchMany(Vec<AppEntry>),
+    Quit,
+}
+
+impl ConfirmAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConfirmAction::Install(_) => "Install",
+            ConfirmAction::Uninstall(_) => "Uninstall",
+            ConfirmAction::Launch(..) | ConfirmAction::LaunchMany(_) => "Launch",
+            ConfirmAction::Quit => "Quit",
+        }
+    }
+}
+
+/// A Yes/No modal. Anything that needs confirmation opens one with `App::ask_confirm`.
+pub struct ConfirmDialog {
+    pub title: String,
+    pub message: String,
+    pub action: ConfirmAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortMode {
+    #[default]
+    Catalog,
+    Name,
+    MostLaunched,
+}
+
+impl SortMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Catalog => "catalog order",
+            SortMode::Name => "name",
+            SortMode::MostLaunched => "most launched",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            SortMode::Catalog => SortMode::Name,
+            SortMode::Name => SortMode::MostLaunched,
+            SortMode::MostLaunched => SortMode::Catalog,
+        }
+    }
+}
+
+/// How `L` opens several selected apps at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LaunchMode {
+    /// A tmux session (or window) per app.
+    #[default]
+    Separate,
+    /// One window with a tiled pane per app.
+    Split,
+}
+
+impl LaunchMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LaunchMode::Separate => "separate sessions",
+            LaunchMode::Split => "split panes",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            LaunchMode::Separate => LaunchMode::Split,
+            LaunchMode::Split => LaunchMode::Separate,
+        }
+    }
+}
+
+/// One row of the catalog list: an entry, or a category header when the All tab is grouped.
+#[derive(Debug, Clone)]
+pub enum ListRow {
+    Header {
+        category: String,
+        count: usize,
+        collapsed: bool,
+    },
+    Entry(usize),
+}
+
+impl ListRow {
+    /// Navigation skips expanded headers; collapsed ones stay reachable so they can be reopened.
+    pub fn is_selectable(&self) -> bool {
+        !matches!(
+            self,
+            ListRow::Header {
+                collapsed: false,
+                ..
+            }
+        )
+    }
+}
+
+/// Captured output of the most recent failed install/uninstall, viewed with `E`.
+pub struct FailureReport {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// How one app fared in an install or uninstall batch.
+pub struct BatchOutcome {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+/// Per-app results shown after a batch of more than one app; any key dismisses it.
+pub struct BatchReport {
+    pub title: String,
+    pub outcomes: Vec<BatchOutcome>,
+    /// How the closing tally names ok and failed outcomes.
+    pub tally_words: (&'static str, &'static str),
+}
+
+/// What a catalog reload changed, by id; shown until any key dismisses it.
+#[derive(Default)]
+pub struct CatalogDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl CatalogDiff {
+    pub fn between(old: &[AppEntry], new: &[AppEntry]) -> Self {
+        let old_by_id: HashMap<&str, &AppEntry> =
+            old.iter().map(|entry| (entry.id.as_str(), entry)).collect();
+        let new_ids: HashSet<&str> = new.iter().map(|entry| entry.id.as_str()).collect();
+
+        let mut diff = Self::default();
+        for entry in new {
+            match old_by_id.get(entry.id.as_str()) {
+                None => diff.added.push(entry.id.clone()),
+                Some(previous) if *previous != entry => diff.modified.push(entry.id.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .iter()
+            .filter(|entry| !new_ids.contains(entry.id.as_str()))
+            .map(|entry| entry.id.clone())
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
 }
 
 #[derive(Clone)]
@@ -25,117 +204,720 @@ pub enum LogLevel {
     Info,
 }
 
+#[derive(Clone, Copy)]
+pub struct LogRetention {
+    pub info: std::time::Duration,
+    pub error: std::time::Duration,
+}
+
+impl Default for LogRetention {
+    fn default() -> Self {
+        Self {
+            info: std::time::Duration::from_secs(3),
+            error: std::time::Duration::from_secs(8),
+        }
+    }
+}
+
+/// A foldable part of the detail panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DetailSection {
+    Info,
+    Description,
+    Commands,
+}
+
+impl DetailSection {
+    pub const ALL: [Self; 3] = [Self::Info, Self::Description, Self::Commands];
+
+    /// The section `step` places after this one, wrapping around.
+    pub fn cycle(self, step: isize) -> Self {
+        let pos = Self::ALL.iter().position(|s| *s == self).unwrap_or(0) as isize;
+        Self::ALL[(pos + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DetailSection::Info => "Info",
+            DetailSection::Description => "Description",
+            DetailSection::Commands => "Commands",
+        }
+    }
+}
+
+/// Where the clickable parts were drawn last frame, for mouse hit-testing.
+#[derive(Default)]
+pub struct ClickAreas {
+    pub tabs: Vec<Rect>,
+    pub categories: Vec<Rect>,
+    /// The list rows, below the column header.
+    pub list: Rect,
+}
+
+/// How long the event loop waits for input before redrawing on its own.
+#[derive(Clone, Copy)]
+pub struct PollInterval {
+    /// While the screen changes by itself, e.g. footer messages waiting to expire.
+    pub active: std::time::Duration,
+    /// While nothing changes until a key is pressed.
+    pub idle: std::time::Duration,
+}
+
+impl Default for PollInterval {
+    fn default() -> Self {
+        Self {
+            active: std::time::Duration::from_millis(100),
+            idle: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+/// Guards against a select-all + L opening dozens of sessions.
+#[derive(Clone, Copy)]
+pub struct LaunchLimits {
+    /// Launching more apps than this at once asks first.
+    pub confirm_above: usize,
+    /// Launching more apps than this at once is refused.
+    pub max: usize,
+}
+
+impl Default for LaunchLimits {
+    fn default() -> Self {
+        Self {
+            confirm_above: 5,
+            max: 20,
+        }
+    }
+}
+
+impl LogRetention {
+    pub fn for_level(&self, level: LogLevel) -> std::time::Duration {
+        match level {
+            LogLevel::Error => self.error,
+            LogLevel::Success | LogLevel::Info => self.info,
+        }
+    }
+}
+
 pub struct App {
+    pub catalog_path: PathBuf,
+    /// Where a remote catalog is fetched from; `catalog_path` is then its cached copy.
+    pub catalog_url: Option<String>,
+    pub catalog_watcher: Option<CatalogWatcher>,
     pub entries: Vec<AppEntry>,
     pub installed_ids: HashSet<String>,
+    pub conflict_ids: HashSet<String>,
+    verify_cache: VerifyCache,
+    /// Installed version by app id, for apps older than their catalog `version`.
+    pub outdated: HashMap<String, String>,
+    pub versions_checked: bool,
+    /// Outdated versions from a running `V` check, which runs off the UI thread.
+    version_check: Option<Receiver<HashMap<String, String>>>,
     pub selected_tab: usize,
     pub categories: Vec<String>,
+    /// Entry indices per name in `categories`, rebuilt whenever the entries change.
+    pub category_index: HashMap<String, Vec<usize>>,
+    pub category_totals: Vec<usize>,
+    pub category_installed: Vec<usize>,
     pub selected_category: usize,
+    pub tags: Vec<String>,
+    pub active_tags: BTreeSet<String>,
+    pub tag_match_all: bool,
+    pub tag_mode: bool,
+    pub tag_cursor: usize,
     pub filtered_indices: Vec<usize>,
+    /// What the list actually shows; `list_state` indexes into this.
+    pub list_rows: Vec<ListRow>,
+    pub group_by_category: bool,
+    pub collapsed_categories: HashSet<String>,
+    pub hide_unavailable: bool,
+    /// Shift+F: only pinned apps, on every tab.
+    pub favorites_only: bool,
+    /// Shift+H: also list entries the catalog marks `hidden`.
+    pub show_hidden: bool,
+    /// Off with `--no-icons` or in ASCII mode.
+    pub show_icons: bool,
+    /// How many otherwise-matching apps `hide_unavailable` removed from the list.
+    pub hidden_unavailable: usize,
     pub list_state: ListState,
+    pub detail_scroll: u16,
     pub selected_ids: HashSet<String>,
+    pub dormant_selected_ids: HashSet<String>,
+    pub favorites: HashSet<String>,
+    /// Ids the user has already seen; anything else in the catalog is badged "new".
+    pub known_ids: HashSet<String>,
+    pub new_ids: HashSet<String>,
+    pub last_launched: HashMap<String, DateTime<Utc>>,
+    pub launch_counts: HashMap<String, u32>,
+    pub sort_mode: SortMode,
+    pub launch_mode: LaunchMode,
+    /// The tmux session opened by the most recent launch this run, for `Y`.
+    pub last_session: Option<String>,
     pub search_mode: bool,
     pub search_input: String,
+    pub search_regex: Option<Regex>,
+    pub search_regex_error: Option<String>,
+    /// Applied searches, newest first, capped at `SEARCH_HISTORY_LIMIT`.
+    pub search_history: VecDeque<String>,
+    /// Position in `search_history` while recalling with Up/Down.
+    pub history_cursor: Option<usize>,
+    /// What was typed before recalling history, restored when Down walks past the newest.
+    pub search_draft: String,
+    pub help_mode: bool,
+    pub stats_mode: bool,
+    pub failure_report: Option<FailureReport>,
+    pub batch_report: Option<BatchReport>,
+    pub reload_diff: Option<CatalogDiff>,
+    /// Id of the entry the detail panel stays on while the list moves.
+    pub pinned_detail: Option<String>,
+    /// Details list every platform's raw command instead of just this platform's.
+    pub show_all_commands: bool,
+    /// Detail sections folded away; kept across entries and runs.
+    pub collapsed_sections: HashSet<DetailSection>,
+    /// The section `=` folds or unfolds.
+    pub detail_focus: DetailSection,
+    pub click_areas: ClickAreas,
+    pub install_queue: InstallQueue,
+    pub output_mode: bool,
+    pub output_scroll: u16,
     pub status: String,
+    pub status_level: LogLevel,
     pub platform: Platform,
-    pub confirm_mode: bool,
-    pub confirm_action: Option<ConfirmAction>,
+    pub platform_overridden: bool,
+    pub confirm_dialog: Option<ConfirmDialog>,
     pub confirm_selected: bool,
+    /// Apps removed by the most recent uninstall, offered for reinstall by Ctrl+Z.
+    pub last_uninstalled: Vec<AppEntry>,
     pub logs: Vec<LogEntry>,
+    pub log_retention: LogRetention,
+    pub launch_limits: LaunchLimits,
+    pub poll_interval: PollInterval,
+    pub tmux_target: TmuxTarget,
+    pub pause_after_command: PauseAfter,
+    /// Ask before `q` quits. Ctrl+C always quits straight away.
+    pub confirm_quit: bool,
+    /// Global `[env]` from the config, applied to every install/uninstall command.
+    pub command_env: BTreeMap<String, String>,
+    pub theme: Theme,
+    pub theme_preset: ThemePreset,
 }
 
 impl App {
-    pub fn new(entries: Vec<AppEntry>) -> Self {
-        let mut categories: Vec<String> = entries
-            .iter()
-            .map(|entry| entry.category.clone())
-            .collect::<BTreeSet<_>>()
-            .into_iter()
-            .collect();
-        if categories.is_empty() {
-            categories.push("uncategorized".to_string());
-        }
+    /// An app restored from the session saved in the state dir.
+    pub fn new(entries: Vec<AppEntry>, catalog_path: impl Into<PathBuf>) -> Self {
+        Self::with_state(entries, catalog_path, PersistedState::load())
+    }
 
+    /// An app restored from `persisted` rather than from disk.
+    pub fn with_state(
+        entries: Vec<AppEntry>,
+        catalog_path: impl Into<PathBuf>,
+        persisted: PersistedState,
+    ) -> Self {
         let mut app = Self {
-            entries,
+            catalog_path: catalog_path.into(),
+            catalog_url: None,
+            catalog_watcher: None,
+            entries: Vec::new(),
             installed_ids: HashSet::new(),
+            conflict_ids: HashSet::new(),
+            verify_cache: VerifyCache::default(),
+            outdated: HashMap::new(),
+            versions_checked: false,
+            version_check: None,
             selected_tab: 0,
-            categories,
+            categories: Vec::new(),
+            category_index: HashMap::new(),
+            category_totals: Vec::new(),
+            category_installed: Vec::new(),
             selected_category: 0,
+            tags: Vec::new(),
+            active_tags: BTreeSet::new(),
+            tag_match_all: true,
+            tag_mode: false,
+            tag_cursor: 0,
             filtered_indices: Vec::new(),
+            list_rows: Vec::new(),
+            group_by_category: false,
+            collapsed_categories: HashSet::new(),
+            hide_unavailable: false,
+            favorites_only: false,
+            show_hidden: false,
+            show_icons: true,
+            hidden_unavailable: 0,
             list_state: ListState::default(),
+            detail_scroll: 0,
             selected_ids: HashSet::new(),
+            dormant_selected_ids: HashSet::new(),
+            favorites: HashSet::new(),
+            known_ids: HashSet::new(),
+            new_ids: HashSet::new(),
+            last_launched: HashMap::new(),
+            launch_counts: HashMap::new(),
+            sort_mode: SortMode::default(),
+            launch_mode: LaunchMode::default(),
+            last_session: None,
             search_mode: false,
             search_input: String::new(),
+            search_regex: None,
+            search_regex_error: None,
+            search_history: VecDeque::new(),
+            history_cursor: None,
+            search_draft: String::new(),
+            help_mode: false,
+            stats_mode: false,
+            failure_report: None,
+            batch_report: None,
+            reload_diff: None,
+            pinned_detail: None,
+            show_all_commands: false,
+            collapsed_sections: HashSet::new(),
+            detail_focus: DetailSection::Info,
+            click_areas: ClickAreas::default(),
+            install_queue: InstallQueue::default(),
+            output_mode: false,
+            output_scroll: 0,
             status: "Ready. Navigate with arrows/jk. Space select, I install, L launch, / search."
                 .to_string(),
+            status_level: LogLevel::Info,
             platform: Platform::detect(),
-            confirm_mode: false,
-            confirm_action: None,
+            platform_overridden: false,
+            confirm_dialog: None,
             confirm_selected: false,
+            last_uninstalled: Vec::new(),
             logs: Vec::new(),
+            log_retention: LogRetention::default(),
+            launch_limits: LaunchLimits::default(),
+            poll_interval: PollInterval::default(),
+            tmux_target: TmuxTarget::default(),
+            pause_after_command: PauseAfter::default(),
+            confirm_quit: false,
+            command_env: BTreeMap::new(),
+            theme: Theme::default(),
+            theme_preset: ThemePreset::default(),
+        };
+        app.dormant_selected_ids = persisted.selected_ids.into_iter().collect();
+        app.favorites = persisted.favorites.into_iter().collect();
+        app.last_launched = persisted
+            .last_launched
+            .into_iter()
+            .filter_map(|(id, secs)| Some((id, DateTime::from_timestamp(secs, 0)?)))
+            .collect();
+        app.launch_counts = persisted.launch_counts.into_iter().collect();
+        app.sort_mode = persisted.sort_mode;
+        // Without a saved list this is the first run, and nothing should look new.
+        app.known_ids = match persisted.known_ids {
+            Some(known) => known.into_iter().collect(),
+            None => entries.iter().map(|entry| entry.id.clone()).collect(),
         };
-        app.refresh_installed_cache();
+        app.search_history = persisted
+            .search_history
+            .into_iter()
+            .take(SEARCH_HISTORY_LIMIT)
+            .collect();
+        app.collapsed_sections = persisted.collapsed_sections.into_iter().collect();
+        app.set_entries(entries);
+        app.restore_view(persisted.selected_tab, persisted.category, persisted.search);
         app
     }
 
+    /// Applies a saved view, clamping anything that no longer fits the catalog.
+    fn restore_view(&mut self, tab: usize, category: Option<String>, search: String) {
+        self.selected_tab = if tab < TABS.len() { tab } else { 0 };
+        self.selected_category = category
+            .and_then(|saved| {
+                self.categories
+                    .iter()
+                    .position(|cat| cat.eq_ignore_ascii_case(&saved))
+            })
+            .unwrap_or(0);
+        self.search_input = search;
+    }
+
+    pub fn override_platform(&mut self, platform: Platform) {
+        self.platform = platform;
+        self.platform_overridden = true;
+    }
+
+    pub fn save_state(&self) -> anyhow::Result<()> {
+        let mut selected_ids: Vec<String> = self
+            .selected_ids
+            .union(&self.dormant_selected_ids)
+            .cloned()
+            .collect();
+        selected_ids.sort();
+        let mut favorites: Vec<String> = self.favorites.iter().cloned().collect();
+        favorites.sort();
+        PersistedState {
+            selected_ids,
+            favorites,
+            last_launched: self
+                .last_launched
+                .iter()
+                .map(|(id, at)| (id.clone(), at.timestamp()))
+                .collect(),
+            launch_counts: self
+                .launch_counts
+                .iter()
+                .map(|(id, count)| (id.clone(), *count))
+                .collect(),
+            sort_mode: self.sort_mode,
+            selected_tab: self.selected_tab,
+            category: self.categories.get(self.selected_category).cloned(),
+            search: self.search_input.clone(),
+            search_history: self.search_history.iter().cloned().collect(),
+            collapsed_sections: DetailSection::ALL
+                .into_iter()
+                .filter(|section| self.collapsed_sections.contains(section))
+                .collect(),
+            known_ids: Some({
+                let mut known: Vec<String> = self.known_ids.iter().cloned().collect();
+                known.sort();
+                known
+            }),
+        }
+        .save()
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<AppEntry>) {
+        let previous_category = self.categories.get(self.selected_category).cloned();
+
+        let mut categories: Vec<String> = entries
+            .iter()
+            .map(|entry| entry.category.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if categories.is_empty() {
+            categories.push("uncategorized".to_string());
+        }
+        self.category_index = categories
+            .iter()
+            .map(|cat| {
+                let members = entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, entry)| entry.category.eq_ignore_ascii_case(cat))
+                    .map(|(index, _)| index)
+                    .collect();
+                (cat.clone(), members)
+            })
+            .collect();
+        self.category_totals = categories
+            .iter()
+            .map(|cat| self.category_members(cat).len())
+            .collect();
+
+        self.selected_category = previous_category
+            .and_then(|prev| categories.iter().position(|cat| *cat == prev))
+            .unwrap_or(0);
+        self.categories = categories;
+
+        self.tags = entries
+            .iter()
+            .flat_map(|entry| entry.tags.iter().map(|tag| tag.to_ascii_lowercase()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        self.active_tags.retain(|tag| self.tags.contains(tag));
+        self.tag_cursor = self.tag_cursor.min(self.tags.len().saturating_sub(1));
+        // Ids missing from the catalog stay dormant so they re-select if they come back.
+        let known_ids: HashSet<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+        let (present, absent): (HashSet<String>, HashSet<String>) = self
+            .selected_ids
+            .drain()
+            .chain(self.dormant_selected_ids.drain())
+            .partition(|id| known_ids.contains(id.as_str()));
+        self.selected_ids = present;
+        self.dormant_selected_ids = absent;
+        self.entries = entries;
+        self.new_ids = self
+            .entries
+            .iter()
+            .filter(|entry| !self.known_ids.contains(&entry.id))
+            .map(|entry| entry.id.clone())
+            .collect();
+        self.refresh_installed_cache();
+    }
+
     pub fn log(&mut self, message: String, level: LogLevel) {
-        let now = std::time::Instant::now();
-        self.logs
-            .retain(|l| now.duration_since(l.created_at) < std::time::Duration::from_secs(3));
+        self.prune_logs();
         self.logs.push(LogEntry {
             message,
             level,
-            created_at: now,
+            created_at: std::time::Instant::now(),
         });
         if self.logs.len() > 3 {
             self.logs.remove(0);
         }
     }
 
+    /// Keeps the output tail of a failed command. Returns whether there was any to keep.
+    pub fn record_failure(&mut self, title: String, err: &anyhow::Error) -> bool {
+        let Some(lines) = failure_output(err) else {
+            return false;
+        };
+        self.failure_report = Some(FailureReport {
+            title,
+            lines: lines.to_vec(),
+        });
+        true
+    }
+
+    /// Key presses wake the loop either way; this only bounds how stale the screen gets.
+    pub fn next_poll(&self) -> std::time::Duration {
+        if self.logs.is_empty() && !self.install_queue.is_busy() && !self.is_checking_versions() {
+            self.poll_interval.idle
+        } else {
+            self.poll_interval.active
+        }
+    }
+
+    pub fn prune_logs(&mut self) {
+        let now = std::time::Instant::now();
+        let retention = self.log_retention;
+        self.logs
+            .retain(|l| now.duration_since(l.created_at) < retention.for_level(l.level));
+    }
+
+    /// Re-probes every entry. `verify` commands only run again for binaries that changed
+    /// since they last ran.
     pub fn refresh_installed_cache(&mut self) {
-        self.installed_ids = self
+        self.installed_ids.clear();
+        self.conflict_ids.clear();
+        let states: Vec<(String, InstallState)> = self
             .entries
             .iter()
-            .filter(|entry| is_binary_installed(&entry.binary))
-            .map(|entry| entry.id.clone())
+            .map(|entry| {
+                let state =
+                    detect_install_state_cached(entry, self.platform, &mut self.verify_cache);
+                (entry.id.clone(), state)
+            })
             .collect();
+        for (id, state) in states {
+            self.set_install_state(&id, state);
+        }
+        self.recount_category_installed();
+    }
+
+    /// Re-probes one entry after its install command exited 0, without re-probing every
+    /// other entry. A zero exit alone doesn't prove the binary landed or passes `verify`.
+    pub fn reprobe_installed(&mut self, id: &str) -> InstallState {
+        let state = self
+            .entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .map_or(InstallState::Missing, |entry| {
+                detect_install_state_cached(entry, self.platform, &mut self.verify_cache)
+            });
+        self.outdated.remove(id);
+        self.set_install_state(id, state);
+        self.recount_category_installed();
+        state
+    }
+
+    fn set_install_state(&mut self, id: &str, state: InstallState) {
+        self.installed_ids.remove(id);
+        self.conflict_ids.remove(id);
+        match state {
+            InstallState::Installed => {
+                self.installed_ids.insert(id.to_string());
+            }
+            InstallState::Conflict => {
+                self.conflict_ids.insert(id.to_string());
+            }
+            InstallState::Missing => {}
+        }
+    }
+
+    /// Records a successful uninstall without re-probing every other entry.
+    pub fn mark_uninstalled(&mut self, id: &str) {
+        self.conflict_ids.remove(id);
+        self.outdated.remove(id);
+        self.installed_ids.remove(id);
+        self.recount_category_installed();
+    }
+
+    fn recount_category_installed(&mut self) {
+        self.category_installed = self
+            .categories
+            .iter()
+            .map(|cat| {
+                self.category_members(cat)
+                    .iter()
+                    .filter(|&&index| self.is_installed(&self.entries[index]))
+                    .count()
+            })
+            .collect();
+    }
+
+    pub fn category_members(&self, category: &str) -> &[usize] {
+        self.category_index
+            .get(category)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
     }
 
     pub fn is_installed(&self, entry: &AppEntry) -> bool {
         self.installed_ids.contains(&entry.id)
     }
 
+    /// Starts running every installed entry's `version_command` on a background thread.
+    /// Returns false if a check is already running.
+    pub fn check_updates(&mut self) -> bool {
+        if self.is_checking_versions() {
+            return false;
+        }
+        let installed: Vec<AppEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| self.installed_ids.contains(&entry.id))
+            .cloned()
+            .collect();
+        let platform = self.platform;
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let outdated = installed
+                .iter()
+                .filter_map(|entry| {
+                    outdated_version(entry, platform).map(|version| (entry.id.clone(), version))
+                })
+                .collect();
+            let _ = tx.send(outdated);
+        });
+        self.version_check = Some(rx);
+        true
+    }
+
+    pub fn is_checking_versions(&self) -> bool {
+        self.version_check.is_some()
+    }
+
+    /// Takes the result of a finished [`check_updates`](Self::check_updates). Returns how many
+    /// apps are outdated, or `None` while the check is still running.
+    pub fn finish_version_check(&mut self) -> Option<usize> {
+        let outdated = match self.version_check.as_ref()?.try_recv() {
+            Ok(outdated) => outdated,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => HashMap::new(),
+        };
+        self.version_check = None;
+        // Apps uninstalled while the check ran are not outdated any more.
+        self.outdated = outdated;
+        self.outdated
+            .retain(|id, _| self.installed_ids.contains(id));
+        self.versions_checked = true;
+        Some(self.outdated.len())
+    }
+
+    /// The cached state from the last probe, not a fresh PATH lookup.
+    pub fn install_state(&self, entry: &AppEntry) -> InstallState {
+        if self.is_conflict(entry) {
+            InstallState::Conflict
+        } else if self.is_installed(entry) {
+            InstallState::Installed
+        } else {
+            InstallState::Missing
+        }
+    }
+
+    /// The binary is on PATH but the entry's `verify` command says it is a different program.
+    pub fn is_conflict(&self, entry: &AppEntry) -> bool {
+        self.conflict_ids.contains(&entry.id)
+    }
+
+    /// Grouping only applies to the All tab.
+    pub fn is_grouped(&self) -> bool {
+        self.group_by_category && self.selected_tab == 0
+    }
+
+    /// Index into `entries` of the focused row, if it is an entry rather than a header.
+    pub fn focused_index(&self) -> Option<usize> {
+        match self.list_rows.get(self.list_state.selected()?)? {
+            ListRow::Entry(idx) => Some(*idx),
+            ListRow::Header { .. } => None,
+        }
+    }
+
+    /// The category of the focused header, or of the focused entry.
+    pub fn focused_category(&self) -> Option<String> {
+        match self.list_rows.get(self.list_state.selected()?)? {
+            ListRow::Header { category, .. } => Some(category.clone()),
+            ListRow::Entry(idx) => self.entries.get(*idx).map(|entry| entry.category.clone()),
+        }
+    }
+
     pub fn current_entry(&self) -> Option<&AppEntry> {
-        let idx = self.list_state.selected()?;
-        let entry_idx = *self.filtered_indices.get(idx)?;
-        self.entries.get(entry_idx)
+        self.entries.get(self.focused_index()?)
     }
 
-    pub fn move_down(&mut self) {
-        if self.filtered_indices.is_empty() {
-            self.list_state.select(None);
-            return;
+    /// The entry pinned with `B`, if it is still in the catalog.
+    pub fn pinned_entry(&self) -> Option<&AppEntry> {
+        let id = self.pinned_detail.as_deref()?;
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// What the detail panel shows: the pinned entry, else the focused one.
+    pub fn detail_entry(&self) -> Option<&AppEntry> {
+        self.pinned_entry().or_else(|| self.current_entry())
+    }
+
+    pub fn select_entry_index(&mut self, idx: usize) -> bool {
+        let pos = self
+            .list_rows
+            .iter()
+            .position(|row| matches!(row, ListRow::Entry(i) if *i == idx));
+        if pos.is_some() {
+            self.list_state.select(pos);
         }
+        pos.is_some()
+    }
 
-        let next = match self.list_state.selected() {
-            Some(i) if i + 1 < self.filtered_indices.len() => i + 1,
-            _ => 0,
-        };
-        self.list_state.select(Some(next));
+    /// Selects the first selectable row at or after `pos`, falling back to the nearest one
+    /// before it.
+    pub fn select_row_near(&mut self, pos: usize) {
+        let pos = pos.min(self.list_rows.len().saturating_sub(1));
+        let found = (pos..self.list_rows.len())
+            .chain((0..pos).rev())
+            .find(|&i| self.list_rows[i].is_selectable());
+        self.list_state.select(found);
+    }
+
+    pub fn move_down(&mut self) {
+        self.step_selection(1);
     }
 
     pub fn move_up(&mut self) {
-        if self.filtered_indices.is_empty() {
+        self.step_selection(self.list_rows.len().saturating_sub(1));
+    }
+
+    /// Moves by `step` rows (mod the row count), wrapping and skipping unselectable rows.
+    fn step_selection(&mut self, step: usize) {
+        let len = self.list_rows.len();
+        if !self.list_rows.iter().any(ListRow::is_selectable) {
             self.list_state.select(None);
             return;
         }
 
-        let prev = match self.list_state.selected() {
-            Some(0) | None => self.filtered_indices.len() - 1,
-            Some(i) => i.saturating_sub(1),
+        let mut pos = match self.list_state.selected() {
+            Some(i) => (i + step) % len,
+            None if step == 1 => 0,
+            None => len - 1,
         };
-        self.list_state.select(Some(prev));
+        while !self.list_rows[pos].is_selectable() {
+            pos = (pos + step) % len;
+        }
+        self.list_state.select(Some(pos));
+        self.detail_scroll = 0;
+    }
+
+    pub fn scroll_detail_down(&mut self, lines: u16) {
+        self.detail_scroll = self.detail_scroll.saturating_add(lines);
+    }
+
+    pub fn scroll_detail_up(&mut self, lines: u16) {
+        self.detail_scroll = self.detail_scroll.saturating_sub(lines);
     }
 
     pub fn toggle_selected_current(&mut self) {
@@ -150,6 +932,54 @@ impl App {
         }
     }
 
+    /// Entries with a non-empty install command for the current platform.
+    pub fn installable_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| command_for_platform(&entry.install, self.platform).is_some())
+            .count()
+    }
+
+    pub fn is_favorite(&self, entry: &AppEntry) -> bool {
+        self.favorites.contains(&entry.id)
+    }
+
+    pub fn is_new(&self, entry: &AppEntry) -> bool {
+        self.new_ids.contains(&entry.id)
+    }
+
+    /// Acknowledges every "new" entry. Returns how many there were.
+    pub fn mark_new_seen(&mut self) -> usize {
+        let count = self.new_ids.len();
+        self.known_ids.extend(self.new_ids.drain());
+        count
+    }
+
+    /// Pins or unpins the focused entry. Returns its name and whether it is now pinned.
+    pub fn toggle_favorite_current(&mut self) -> Option<(String, bool)> {
+        let entry = self.current_entry()?;
+        let (id, name) = (entry.id.clone(), entry.name.clone());
+        let pinned = if self.favorites.remove(&id) {
+            false
+        } else {
+            self.favorites.insert(id);
+            true
+        };
+        Some((name, pinned))
+    }
+
+    pub fn launch_count(&self, entry: &AppEntry) -> u32 {
+        self.launch_counts.get(&entry.id).copied().unwrap_or(0)
+    }
+
+    pub fn record_launch(&mut self, id: &str) {
+        self.last_launched.insert(id.to_string(), Utc::now());
+        *self.launch_counts.entry(id.to_string()).or_default() += 1;
+        if let Err(e) = self.save_state() {
+            self.log(format!("Launch time not saved: {e:#}"), LogLevel::Error);
+        }
+    }
+
     pub fn selected_entries(&self) -> Vec<AppEntry> {
         let mut results: Vec<AppEntry> = self
             .entries
@@ -167,35 +997,188 @@ impl App {
         results
     }
 
+    /// Opens the confirm dialog with "Yes" focused.
+    pub fn ask_confirm<T: Into<String>, M: Into<String>>(
+        &mut self,
+        title: T,
+        message: M,
+        action: ConfirmAction,
+    ) {
+        self.confirm_dialog = Some(ConfirmDialog {
+            title: title.into(),
+            message: message.into(),
+            action,
+        });
+        self.confirm_selected = true;
+    }
+
     pub fn set_status<S: Into<String>>(&mut self, message: S) {
+        self.set_status_level(message, LogLevel::Info);
+    }
+
+    /// Like [`App::set_status`], for messages the footer should color as a success or error.
+    pub fn set_status_level<S: Into<String>>(&mut self, message: S, level: LogLevel) {
         self.status = message.into();
+        self.status_level = level;
     }
 
     pub fn clear_selection(&mut self) {
         self.selected_ids.clear();
+        self.dormant_selected_ids.clear();
     }
 
-    pub fn matches_tab(&self, entry: &AppEntry) -> bool {
+    /// Entry indices shown by the current tab, in catalog order. The Categories tab reads
+    /// them straight from `category_index` instead of scanning every entry.
+    pub fn tab_indices(&self) -> Vec<usize> {
         match self.selected_tab {
-            0 => true,
-            1 => self.is_installed(entry),
-            2 => self
-                .categories
-                .get(self.selected_category)
-                .map(|cat| entry.category.eq_ignore_ascii_case(cat))
-                .unwrap_or(true),
-            _ => true,
+            1 => (0..self.entries.len())
+                .filter(|&index| self.is_installed(&self.entries[index]))
+                .collect(),
+            2 => match self.categories.get(self.selected_category) {
+                Some(cat) => self.category_members(cat).to_vec(),
+                None => (0..self.entries.len()).collect(),
+            },
+            // Only as fresh as the last version check.
+            3 => (0..self.entries.len())
+                .filter(|&index| {
+                    let entry = &self.entries[index];
+                    self.is_installed(entry) && self.outdated.contains_key(&entry.id)
+                })
+                .collect(),
+            _ => (0..self.entries.len()).collect(),
         }
     }
 
+    pub fn matches_tags(&self, entry: &AppEntry) -> bool {
+        if self.active_tags.is_empty() {
+            return true;
+        }
+        let has_tag = |tag: &String| entry.tags.iter().any(|t| t.eq_ignore_ascii_case(tag));
+        if self.tag_match_all {
+            self.active_tags.iter().all(has_tag)
+        } else {
+            self.active_tags.iter().any(has_tag)
+        }
+    }
+
+    pub fn toggle_tag_at_cursor(&mut self) {
+        let Some(tag) = self.tags.get(self.tag_cursor).cloned() else {
+            return;
+        };
+        if !self.active_tags.remove(&tag) {
+            self.active_tags.insert(tag);
+        }
+    }
+
+    /// Remembers an applied search, moving a repeated query to the front.
+    pub fn push_search_history(&mut self) {
+        self.history_cursor = None;
+        let query = self.search_input.trim();
+        if query.is_empty() {
+            return;
+        }
+        let query = query.to_string();
+        self.search_history.retain(|past| *past != query);
+        self.search_history.push_front(query);
+        self.search_history.truncate(SEARCH_HISTORY_LIMIT);
+    }
+
+    /// Steps through `search_history` like shell history: Up (`older`) goes back in time,
+    /// Down returns towards the draft. Returns whether the input changed.
+    pub fn recall_search(&mut self, older: bool) -> bool {
+        let next = match (self.history_cursor, older) {
+            (None, true) if !self.search_history.is_empty() => {
+                self.search_draft = self.search_input.clone();
+                Some(0)
+            }
+            (Some(pos), true) if pos + 1 < self.search_history.len() => Some(pos + 1),
+            (Some(0), false) => None,
+            (Some(pos), false) => Some(pos - 1),
+            _ => return false,
+        };
+        self.history_cursor = next;
+        self.search_input = match next {
+            Some(pos) => self.search_history[pos].clone(),
+            None => std::mem::take(&mut self.search_draft),
+        };
+        true
+    }
+
+    /// A search starting with `/` is a case-insensitive regex over all fields. Recompiled
+    /// whenever the input changes; while the pattern is incomplete or invalid the error is
+    /// kept for the search box and nothing is filtered out.
+    pub fn compile_search_regex(&mut self) {
+        self.search_regex = None;
+        self.search_regex_error = None;
+        let Some(pattern) = self.search_input.strip_prefix('/') else {
+            return;
+        };
+        if pattern.is_empty() {
+            return;
+        }
+        match RegexBuilder::new(pattern).case_insensitive(true).build() {
+            Ok(regex) => self.search_regex = Some(regex),
+            Err(e) => self.search_regex_error = Some(e.to_string()),
+        }
+    }
+
+    pub fn is_regex_search(&self) -> bool {
+        self.search_input.starts_with('/')
+    }
+
     pub fn matches_search(&self, entry: &AppEntry) -> bool {
         if self.search_input.trim().is_empty() {
             return true;
         }
-        let needle = self.search_input.to_ascii_lowercase();
+        if self.is_regex_search() {
+            let Some(regex) = &self.search_regex else {
+                return true;
+            };
+            let haystack = [
+                entry.name.as_str(),
+                entry.id.as_str(),
+                entry.category.as_str(),
+                entry.description.as_str(),
+            ]
+            .into_iter()
+            .chain(entry.aliases.iter().map(String::as_str))
+            .chain(entry.tags.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("\n");
+            return regex.is_match(&haystack);
+        }
+        let query = SearchQuery::parse(&self.search_input);
+        let fields_match = query.field_terms.iter().all(|(field, value)| {
+            let contains = |text: &str| text.to_ascii_lowercase().contains(value.as_str());
+            match field {
+                SearchField::Name => contains(&entry.name),
+                SearchField::Id => {
+                    contains(&entry.id) || entry.aliases.iter().any(|alias| contains(alias))
+                }
+                SearchField::Category => contains(&entry.category),
+                SearchField::Description => contains(&entry.description),
+                SearchField::Tag => entry.tags.iter().any(|tag| contains(tag)),
+            }
+        });
+        if !fields_match {
+            return false;
+        }
+        if !query.has_free_text() {
+            return true;
+        }
+
+        let needle = query.free_text;
         entry.name.to_ascii_lowercase().contains(&needle)
             || entry.description.to_ascii_lowercase().contains(&needle)
             || entry.category.to_ascii_lowercase().contains(&needle)
             || entry.id.to_ascii_lowercase().contains(&needle)
+            || entry
+                .aliases
+                .iter()
+                .any(|alias| alias.to_ascii_lowercase().contains(&needle))
+            || entry
+                .tags
+                .iter()
+                .any(|tag| tag.to_ascii_lowercase().contains(&needle))
     }
 }
@@ -1,44 +1,527 @@
+use std::cmp::Reverse;
 use std::io::Stdout;
-use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Position, Rect};
 use ratatui::{backend::CrosstermBackend, Terminal};
 
-use super::actions::suspend_tui_for_command;
-use super::state::{App, ConfirmAction, LogLevel};
+use super::actions::{suspend_tui_for_command, PauseAfter};
+use super::outcome::{decide_install, decide_launch, decide_uninstall, ActionOutcome};
+use super::queue::{InstallJob, JobKind, QueueEvent};
+use super::state::{
+    App, BatchOutcome, BatchReport, CatalogDiff, ConfirmAction, LaunchMode, ListRow, LogLevel,
+    SortMode, TABS,
+};
+use crate::config::Config;
+use crate::registry::load_catalog;
 use crate::registry::model::AppEntry;
-use crate::system::exec::{command_for_platform, run_install_cmd};
+use crate::system::browser::{looks_like_url, open_path, open_url};
+use crate::system::clipboard::copy_to_clipboard;
+use crate::system::editor::open_in_editor;
+use crate::system::exec::{
+    command_env, command_for_platform, entry_command, install_retries, install_timeout,
+    is_cancelled, program_available, required_programs, run_install_cmd,
+    run_install_cmd_with_retries, take_interrupt, InstallState,
+};
 use crate::system::os::Platform;
-use crate::system::tmux::{has_tmux, launch_in_tmux, tmux_install_hint};
+use crate::system::tmux::{
+    has_tmux, in_tmux_session, launch_in_tmux, launch_split_in_tmux, running_in_tmux, switch_to,
+    tmux_install_hint,
+};
 use crate::ui::draw::ui;
+use crate::ui::glyphs::glyphs;
+use crate::ui::theme::Theme;
+use crate::utils::format_elapsed;
 
 pub fn refresh_filter(app: &mut App) {
+    app.compile_search_regex();
     app.filtered_indices = app
-        .entries
-        .iter()
-        .enumerate()
-        .filter(|(_, entry)| app.matches_tab(entry))
-        .filter(|(_, entry)| app.matches_search(entry))
-        .map(|(index, _)| index)
+        .tab_indices()
+        .into_iter()
+        .filter(|&index| app.show_hidden || !app.entries[index].hidden)
+        .filter(|&index| !app.favorites_only || app.is_favorite(&app.entries[index]))
+        .filter(|&index| app.matches_tags(&app.entries[index]))
+        .filter(|&index| app.matches_search(&app.entries[index]))
         .collect();
+    app.hidden_unavailable = 0;
+    if app.hide_unavailable {
+        let before = app.filtered_indices.len();
+        app.filtered_indices.retain(|&index| {
+            command_for_platform(&app.entries[index].install, app.platform).is_some()
+        });
+        app.hidden_unavailable = before - app.filtered_indices.len();
+    }
+    match app.sort_mode {
+        SortMode::Catalog => {}
+        SortMode::Name => app
+            .filtered_indices
+            .sort_by_cached_key(|&index| app.entries[index].name.to_lowercase()),
+        SortMode::MostLaunched => app
+            .filtered_indices
+            .sort_by_key(|&index| Reverse(app.launch_counts.get(&app.entries[index].id).copied())),
+    }
+    // Stable, so pinned apps float to the top without disturbing the chosen order.
+    app.filtered_indices
+        .sort_by_key(|&index| !app.favorites.contains(&app.entries[index].id));
+
+    app.list_rows = build_rows(app);
+
+    let pos = match app.list_state.selected() {
+        Some(idx) if idx < app.list_rows.len() => idx,
+        _ => 0,
+    };
+    app.select_row_near(pos);
+    app.detail_scroll = 0;
+}
+
+fn build_rows(app: &App) -> Vec<ListRow> {
+    if !app.is_grouped() {
+        return app
+            .filtered_indices
+            .iter()
+            .copied()
+            .map(ListRow::Entry)
+            .collect();
+    }
+
+    let mut rows = Vec::new();
+    for category in &app.categories {
+        let members: Vec<usize> = app
+            .filtered_indices
+            .iter()
+            .copied()
+            .filter(|&idx| app.entries[idx].category == *category)
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        let collapsed = app.collapsed_categories.contains(category);
+        rows.push(ListRow::Header {
+            category: category.clone(),
+            count: members.len(),
+            collapsed,
+        });
+        if !collapsed {
+            rows.extend(members.into_iter().map(ListRow::Entry));
+        }
+    }
+    rows
+}
+
+pub fn reload_catalog(app: &mut App) {
+    let source = app
+        .catalog_url
+        .clone()
+        .unwrap_or_else(|| app.catalog_path.display().to_string());
+    match load_catalog(&source) {
+        Ok(catalog) => {
+            let diff = CatalogDiff::between(&app.entries, &catalog.entries);
+            app.set_entries(catalog.entries);
+            refresh_filter(app);
+            if diff.is_empty() {
+                app.log(
+                    format!("Reloaded {} apps, nothing changed", app.entries.len()),
+                    LogLevel::Success,
+                );
+            } else {
+                app.log(
+                    format!("Reloaded {} apps", app.entries.len()),
+                    LogLevel::Success,
+                );
+                app.reload_diff = Some(diff);
+            }
+            app.set_status_level(
+                format!("Reloaded catalog from {source}."),
+                LogLevel::Success,
+            );
+            if let Some(warning) = catalog.warning {
+                app.log(warning, LogLevel::Error);
+            }
+        }
+        Err(e) => {
+            app.log(format!("Reload failed: {e:#}"), LogLevel::Error);
+            app.set_status_level(
+                format!("Reload failed, keeping current catalog: {e:#}"),
+                LogLevel::Error,
+            );
+        }
+    }
+}
+
+/// Suspends the TUI to edit a local catalog, then reloads it.
+fn edit_catalog(app: &mut App, terminal: &mut Terminal<CrosstermBackend<Stdout>>) {
+    if app.catalog_url.is_some() {
+        app.log(
+            "The catalog comes from a URL; edit it at the source, then press R.".to_string(),
+            LogLevel::Info,
+        );
+        return;
+    }
+    let path = app.catalog_path.clone();
+    let message = format!("Opening {} in your editor...", path.display());
+    let result = suspend_tui_for_command(terminal, &message, PauseAfter::OnError, || {
+        open_in_editor(&path, app.platform)
+    });
+    match result {
+        Ok(()) => reload_catalog(app),
+        Err(e) => app.log(format!("Editor failed: {e:#}"), LogLevel::Error),
+    }
+}
+
+fn toggle_hide_unavailable(app: &mut App) {
+    app.hide_unavailable = !app.hide_unavailable;
+    refresh_filter(app);
+    if app.hide_unavailable {
+        app.set_status(format!(
+            "Hiding apps unavailable on {}.",
+            app.platform.label()
+        ));
+    } else {
+        app.set_status("Showing all apps.");
+    }
+}
+
+fn toggle_favorites_only(app: &mut App) {
+    app.favorites_only = !app.favorites_only;
+    refresh_filter(app);
+    if app.favorites_only {
+        app.set_status("Showing pinned apps only.");
+    } else {
+        app.set_status("Showing all apps.");
+    }
+}
 
-    let new_idx = match app.list_state.selected() {
-        Some(idx) if idx < app.filtered_indices.len() => Some(idx),
-        _ if app.filtered_indices.is_empty() => None,
-        _ => Some(0),
+fn toggle_show_hidden(app: &mut App) {
+    app.show_hidden = !app.show_hidden;
+    refresh_filter(app);
+    if app.show_hidden {
+        app.set_status("Showing entries the catalog marks hidden.");
+    } else {
+        app.set_status("Hiding entries the catalog marks hidden.");
+    }
+}
+
+fn cycle_launch_mode(app: &mut App) {
+    app.launch_mode = app.launch_mode.next();
+    app.set_status(format!("Launch mode: {}", app.launch_mode.label()));
+}
+
+/// Opens all targets as panes of one tmux window. Returns the targets that still need a
+/// separate launch because a split failed.
+fn launch_split<'a>(app: &mut App, targets: &'a [AppEntry]) -> &'a [AppEntry] {
+    let (target_loc, opened) = match launch_split_in_tmux(targets, app.tmux_target) {
+        Ok(result) => result,
+        Err(e) => {
+            app.log(format!("Split launch failed: {}", e), LogLevel::Error);
+            return targets;
+        }
     };
-    app.list_state.select(new_idx);
+    for target in &targets[..opened] {
+        app.record_launch(&target.id);
+    }
+    let names = targets[..opened]
+        .iter()
+        .map(|target| target.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Some(session_name) = target_loc.strip_prefix("session:") {
+        app.log(format!("Session '{}' opened", session_name), LogLevel::Info);
+        app.last_session = Some(session_name.to_string());
+        app.set_status_level(
+            format!(
+                "Launched {} in tmux session '{}'. Attach: tmux attach -t {}",
+                names, session_name, session_name
+            ),
+            LogLevel::Success,
+        );
+    } else if let Some(window_name) = target_loc.strip_prefix("window:") {
+        app.log(format!("Window '{}' opened", window_name), LogLevel::Info);
+        app.set_status_level(
+            format!("Launched {} in tmux window '{}'.", names, window_name),
+            LogLevel::Success,
+        );
+    }
+    if opened < targets.len() {
+        app.log(
+            "Could not split further, launching the rest separately".to_string(),
+            LogLevel::Error,
+        );
+    }
+    &targets[opened..]
+}
+
+/// Launches several apps as split panes or one by one, per the launch mode.
+fn launch_many(app: &mut App, targets: &[AppEntry]) {
+    let mut remaining = targets;
+    if app.launch_mode == LaunchMode::Split && targets.len() > 1 {
+        remaining = launch_split(app, targets);
+    }
+    for target in remaining {
+        launch_separately(app, target);
+    }
+}
+
+/// Launches `target`, unless it already runs in tmux; then asks first so a stray key doesn't
+/// pile up duplicate sessions.
+fn launch_or_confirm(app: &mut App, target: &AppEntry) {
+    match running_in_tmux(target) {
+        Some(existing) => app.ask_confirm(
+            "Already running",
+            format!(
+                "{} is already running in {existing}. Launch another?",
+                target.name
+            ),
+            ConfirmAction::Launch(Box::new(target.clone()), existing),
+        ),
+        None => launch_separately(app, target),
+    }
+}
+
+/// The "No" answer to a duplicate launch: go to the running copy instead.
+fn focus_existing(app: &mut App, existing: &str) {
+    if !in_tmux_session() {
+        let session = existing.split(':').next().unwrap_or(existing);
+        app.last_session = Some(session.to_string());
+        app.log(
+            format!("Not launched. Attach: tmux attach -t {session}"),
+            LogLevel::Info,
+        );
+        return;
+    }
+    match switch_to(existing) {
+        Ok(()) => app.log(format!("Switched to {existing}"), LogLevel::Info),
+        Err(e) => app.log(format!("Error: {e:#}"), LogLevel::Error),
+    }
+}
+
+fn launch_separately(app: &mut App, target: &AppEntry) {
+    let target_name = target.name.clone();
+    match launch_in_tmux(target, app.tmux_target) {
+        Ok(target_loc) => {
+            app.record_launch(&target.id);
+            if let Some(session_name) = target_loc.strip_prefix("session:") {
+                app.log(format!("Session '{}' opened", session_name), LogLevel::Info);
+                app.last_session = Some(session_name.to_string());
+                app.set_status_level(
+                    format!(
+                        "Launched {} in tmux session '{}'. Attach: tmux attach -t {}",
+                        target_name, session_name, session_name
+                    ),
+                    LogLevel::Success,
+                );
+            } else if let Some(window_name) = target_loc.strip_prefix("window:") {
+                app.log(format!("Window '{}' opened", window_name), LogLevel::Info);
+                app.set_status_level(
+                    format!("Launched {} in tmux window '{}'.", target_name, window_name),
+                    LogLevel::Success,
+                );
+            } else {
+                app.log(format!("Launched {}", target_name), LogLevel::Info);
+                app.set_status_level(
+                    format!("Launched {} in tmux.", target_name),
+                    LogLevel::Success,
+                );
+            }
+        }
+        Err(e) => {
+            app.log(format!("Error: {}", e), LogLevel::Error);
+            app.set_status_level(
+                format!("Launch failed for {}: {}", target_name, e),
+                LogLevel::Error,
+            )
+        }
+    }
+}
+
+fn copy_attach_command(app: &mut App) {
+    let Some(session_name) = app.last_session.clone() else {
+        app.log(
+            "No tmux session launched yet; launch an app first.".to_string(),
+            LogLevel::Info,
+        );
+        return;
+    };
+    let command = format!("tmux attach -t {session_name}");
+    match copy_to_clipboard(&command, app.platform) {
+        Ok(()) => app.log(format!("Copied: {command}"), LogLevel::Success),
+        Err(e) => app.log(
+            format!("Could not copy '{command}': {e:#}"),
+            LogLevel::Error,
+        ),
+    }
+}
+
+/// Where the focused app's binary lives, following symlinks so it is clear which package
+/// manager put it there. With `open_dir`, also opens the containing directory.
+fn reveal_binary(app: &mut App, open_dir: bool) {
+    let Some(target) = app.current_entry().cloned() else {
+        app.log("No app focused.".to_string(), LogLevel::Info);
+        return;
+    };
+    let Ok(path) = which::which(target.binary.primary()) else {
+        app.log(
+            format!(
+                "{} not found on PATH; is {} installed?",
+                target.binary, target.name
+            ),
+            LogLevel::Error,
+        );
+        return;
+    };
+    let resolved = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+    let location = if resolved == path {
+        path.display().to_string()
+    } else {
+        format!("{} -> {}", path.display(), resolved.display())
+    };
+    if !open_dir {
+        app.log(format!("{}: {location}", target.name), LogLevel::Info);
+        return;
+    }
+    let Some(dir) = resolved.parent() else {
+        app.log(format!("{}: {location}", target.name), LogLevel::Info);
+        return;
+    };
+    match open_path(dir, app.platform) {
+        Ok(()) => app.log(format!("Opened {}", dir.display()), LogLevel::Info),
+        Err(e) => app.log(
+            format!("Could not open {}: {e:#}", dir.display()),
+            LogLevel::Error,
+        ),
+    }
+}
+
+fn toggle_pinned_detail(app: &mut App) {
+    if app.pinned_entry().is_some() {
+        app.pinned_detail = None;
+        app.log("Details follow the list again.".to_string(), LogLevel::Info);
+        return;
+    }
+    let Some(target) = app.current_entry() else {
+        app.log("No app focused to pin.".to_string(), LogLevel::Info);
+        return;
+    };
+    let message = format!("Details pinned to {}. B unpins.", target.name);
+    app.pinned_detail = Some(target.id.clone());
+    app.log(message, LogLevel::Info);
+}
+
+fn toggle_grouping(app: &mut App) {
+    app.group_by_category = !app.group_by_category;
+    let focused = app.focused_index();
+    refresh_filter(app);
+    if let Some(idx) = focused {
+        app.select_entry_index(idx);
+    }
+    if app.group_by_category {
+        app.set_status("All tab grouped by category. Z folds the focused category.");
+    } else {
+        app.set_status("All tab shown as a flat list.");
+    }
+}
+
+fn toggle_collapse(app: &mut App) {
+    if !app.is_grouped() {
+        app.set_status("Press G on the All tab to group by category first.");
+        return;
+    }
+    let Some(category) = app.focused_category() else {
+        return;
+    };
+    let collapsed = !app.collapsed_categories.remove(&category);
+    if collapsed {
+        app.collapsed_categories.insert(category.clone());
+    }
+    refresh_filter(app);
+
+    let header = app
+        .list_rows
+        .iter()
+        .position(|row| matches!(row, ListRow::Header { category: c, .. } if *c == category));
+    if let Some(pos) = header {
+        // A collapsed header holds the focus; an expanded one hands it to its first entry.
+        app.select_row_near(if collapsed { pos } else { pos + 1 });
+    }
+}
+
+fn cycle_sort(app: &mut App) {
+    app.sort_mode = app.sort_mode.next();
+    refresh_filter(app);
+    app.set_status(format!("Sort: {}", app.sort_mode.label()));
+}
+
+fn mark_new_seen(app: &mut App) {
+    let count = app.mark_new_seen();
+    if count == 0 {
+        app.set_status("No new apps to acknowledge.");
+        return;
+    }
+    match app.save_state() {
+        Ok(()) => app.set_status_level(
+            format!("Marked {count} new apps as seen."),
+            LogLevel::Success,
+        ),
+        Err(e) => app.set_status_level(
+            format!("Marked {count} new apps as seen (not saved: {e:#})"),
+            LogLevel::Error,
+        ),
+    }
+}
+
+fn toggle_favorite(app: &mut App) {
+    let focused = app.focused_index();
+    let Some((name, pinned)) = app.toggle_favorite_current() else {
+        app.set_status("No app focused to pin.");
+        return;
+    };
+    refresh_filter(app);
+    // Re-sorting moves the entry, so keep the cursor on it.
+    if let Some(idx) = focused {
+        app.select_entry_index(idx);
+    }
+
+    let verb = if pinned { "Pinned" } else { "Unpinned" };
+    match app.save_state() {
+        Ok(()) => app.set_status(format!("{verb} {name}.")),
+        Err(e) => {
+            app.set_status_level(format!("{verb} {name} (not saved: {e:#})"), LogLevel::Error)
+        }
+    }
+}
+
+pub fn cycle_theme(app: &mut App) {
+    if app.theme.monochrome {
+        app.set_status("Colors are disabled (NO_COLOR / --no-color).");
+        return;
+    }
+
+    let preset = app.theme_preset.next();
+    let (theme, warnings) = Theme::load(preset);
+    app.theme_preset = preset;
+    app.theme = theme;
+    for warning in warnings {
+        app.log(warning, LogLevel::Error);
+    }
+
+    match Config::store("theme", preset.name()) {
+        Ok(()) => app.set_status(format!("Theme: {}", preset.name())),
+        Err(e) => app.set_status_level(
+            format!("Theme: {} (not saved: {e:#})", preset.name()),
+            LogLevel::Error,
+        ),
+    }
 }
 
 pub fn cycle_tab_right(app: &mut App) {
-    const TABS: [&str; 3] = ["All", "Installed", "Categories"];
     app.selected_tab = (app.selected_tab + 1) % TABS.len();
     refresh_filter(app);
 }
 
 pub fn cycle_tab_left(app: &mut App) {
-    const TABS: [&str; 3] = ["All", "Installed", "Categories"];
     app.selected_tab = if app.selected_tab == 0 {
         TABS.len() - 1
     } else {
@@ -47,6 +530,15 @@ pub fn cycle_tab_left(app: &mut App) {
     refresh_filter(app);
 }
 
+/// Jumps straight to the tab at `index`; out-of-range indices are ignored.
+pub fn select_tab(app: &mut App, index: usize) {
+    if index >= TABS.len() || index == app.selected_tab {
+        return;
+    }
+    app.selected_tab = index;
+    refresh_filter(app);
+}
+
 pub fn category_right(app: &mut App) {
     if app.selected_tab != 2 || app.categories.is_empty() {
         return;
@@ -67,397 +559,1251 @@ pub fn category_left(app: &mut App) {
     refresh_filter(app);
 }
 
+fn install_entries(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    targets: Vec<AppEntry>,
+) {
+    let mut outcomes = Vec::new();
+    let total = targets.len();
+    let batch = total > 1;
+    for (index, target) in targets.into_iter().enumerate() {
+        if app.install_queue.contains(&target.id) {
+            app.log(
+                format!("{} is in the queue; wait for it", target.name),
+                LogLevel::Info,
+            );
+            outcomes.push(outcome(&target, false, "in the queue"));
+            continue;
+        }
+        let install_cmd = match decide_install(&target, app.platform, app.install_state(&target)) {
+            ActionOutcome::Run(cmd) => cmd,
+            ActionOutcome::AlreadyInstalled => {
+                app.set_status(format!("{} already installed", target.name));
+                app.log(format!("{} already installed", target.name), LogLevel::Info);
+                outcomes.push(outcome(&target, true, "already installed"));
+                continue;
+            }
+            _ => {
+                app.set_status_level(
+                    format!(
+                        "No install command defined for {} on {}.",
+                        target.name,
+                        app.platform.label()
+                    ),
+                    LogLevel::Error,
+                );
+                outcomes.push(outcome(
+                    &target,
+                    false,
+                    format!("no install command on {}", app.platform.label()),
+                ));
+                continue;
+            }
+        };
+        if !target.is_interactive() {
+            let queued = format!("Queued {}; it installs in the background.", target.name);
+            app.set_status(queued.clone());
+            app.log(queued, LogLevel::Info);
+            push_install(app, target.clone(), install_cmd);
+            outcomes.push(outcome(&target, true, "queued"));
+            continue;
+        }
+        app.set_status(format!("Installing {} using: {}", target.name, install_cmd));
+
+        let message = format!(
+            "About to run install command for {}.\n\nCommand:\n{}\n\nIf sudo asks for password, type normally. Press Ctrl-C to cancel.",
+            target.name, install_cmd
+        );
+
+        let post_cmd = target
+            .post_install
+            .as_ref()
+            .and_then(|commands| entry_command(&target, commands, app.platform));
+        let env = command_env(&target, &app.command_env);
+        let mut retry_notes = Vec::new();
+        let mut post_result = None;
+        let started = Instant::now();
+        let platform = app.platform;
+        let result = suspend_tui_for_command(terminal, &message, app.pause_after_command, || {
+            run_install_cmd_with_retries(
+                &install_cmd,
+                platform,
+                install_timeout(),
+                &env,
+                install_retries(&target),
+                true,
+                |note| {
+                    eprintln!("{note}");
+                    retry_notes.push(note);
+                },
+            )?;
+            if let Some(cmd) = &post_cmd {
+                println!("\nRunning post-install step: {cmd}");
+                post_result = Some(run_install_cmd(cmd, platform, install_timeout(), &env));
+            }
+            Ok(())
+        });
+        let took = format_elapsed(started.elapsed());
+        for note in retry_notes {
+            app.log(format!("{}: {note}", target.name), LogLevel::Info);
+        }
+
+        match result {
+            Ok(_) => {
+                let warning = settle_install(app, &target, &took);
+                match post_result {
+                    Some(Err(e)) => {
+                        app.log(
+                            format!("Post-install for {} failed: {}", target.name, e),
+                            LogLevel::Error,
+                        );
+                        let hint = output_hint(
+                            app.record_failure(format!("Post-install {}", target.name), &e),
+                        );
+                        app.set_status_level(
+                            format!(
+                                "Installed {}, but its post-install step failed: {}{hint}",
+                                target.name, e
+                            ),
+                            LogLevel::Error,
+                        );
+                        outcomes.push(outcome(
+                            &target,
+                            true,
+                            format!("installed in {took}, post-install failed: {e}"),
+                        ));
+                    }
+                    post_result => {
+                        if post_result.is_some() {
+                            app.log(
+                                format!("Post-install for {} done", target.name),
+                                LogLevel::Info,
+                            );
+                        }
+                        match warning {
+                            Some(warning) => {
+                                app.set_status_level(
+                                    format!(
+                                        "{}: install command exited 0, but {warning}.",
+                                        target.name
+                                    ),
+                                    LogLevel::Error,
+                                );
+                                outcomes.push(outcome(
+                                    &target,
+                                    true,
+                                    format!("exited 0 in {took}, but {warning}"),
+                                ));
+                            }
+                            None => {
+                                app.set_status_level(
+                                    format!("Installed {} successfully in {took}.", target.name),
+                                    LogLevel::Success,
+                                );
+                                outcomes.push(outcome(
+                                    &target,
+                                    true,
+                                    format!("installed in {took}"),
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                app.log(format!("Error: {}", e), LogLevel::Error);
+                let hint = output_hint(app.record_failure(format!("Install {}", target.name), &e));
+                app.set_status_level(
+                    format!("Install failed for {}: {}{hint}", target.name, e),
+                    LogLevel::Error,
+                );
+                outcomes.push(outcome(&target, false, e.to_string()));
+                if is_cancelled(&e) {
+                    log_batch_stopped(app, total - index - 1);
+                    break;
+                }
+            }
+        }
+    }
+    if batch {
+        app.batch_report = Some(BatchReport {
+            title: "Install results".to_string(),
+            outcomes,
+            tally_words: ("succeeded", "failed"),
+        });
+    }
+    refresh_filter(app);
+}
+
+/// Checks that the programs each install command starts with are on PATH, without running
+/// anything, and lists which apps are ready to install.
+fn preflight(app: &mut App, targets: Vec<AppEntry>) {
+    let mut outcomes = Vec::new();
+    for target in &targets {
+        let Some(cmd) = entry_command(target, &target.install, app.platform) else {
+            outcomes.push(outcome(
+                target,
+                false,
+                format!("no install command on {}", app.platform.label()),
+            ));
+            continue;
+        };
+        let programs = required_programs(&cmd, app.platform);
+        if programs.is_empty() {
+            outcomes.push(outcome(target, true, "nothing to check".to_string()));
+            continue;
+        }
+        let checks: Vec<(String, bool)> = programs
+            .into_iter()
+            .map(|program| {
+                let found = program_available(&program, app.platform);
+                (program, found)
+            })
+            .collect();
+        let detail = checks
+            .iter()
+            .map(|(program, found)| {
+                if *found {
+                    format!("{} {program} available", glyphs().ok)
+                } else {
+                    format!("{} {program} missing", glyphs().failed)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        outcomes.push(outcome(
+            target,
+            checks.iter().all(|(_, found)| *found),
+            detail,
+        ));
+    }
+    let blocked = outcomes.iter().filter(|outcome| !outcome.ok).count();
+    app.log(
+        format!(
+            "Preflight: {} of {} ready to install",
+            outcomes.len() - blocked,
+            outcomes.len()
+        ),
+        if blocked == 0 {
+            LogLevel::Success
+        } else {
+            LogLevel::Error
+        },
+    );
+    app.batch_report = Some(BatchReport {
+        title: "Preflight (nothing was run)".to_string(),
+        outcomes,
+        tally_words: ("ready", "not ready"),
+    });
+}
+
+/// Hands installs to the background worker so browsing can go on meanwhile.
+fn queue_installs(app: &mut App, targets: Vec<AppEntry>) {
+    let mut queued = 0;
+    for target in targets {
+        if app.install_queue.contains(&target.id) {
+            app.log(format!("{} is already queued", target.name), LogLevel::Info);
+            continue;
+        }
+        let cmd = match decide_install(&target, app.platform, app.install_state(&target)) {
+            ActionOutcome::Run(cmd) => cmd,
+            ActionOutcome::AlreadyInstalled => {
+                app.log(format!("{} already installed", target.name), LogLevel::Info);
+                continue;
+            }
+            _ => {
+                app.log(
+                    format!(
+                        "No install command defined for {} on {}",
+                        target.name,
+                        app.platform.label()
+                    ),
+                    LogLevel::Error,
+                );
+                continue;
+            }
+        };
+        push_install(app, target, cmd);
+        queued += 1;
+    }
+    if queued > 0 {
+        app.log(
+            format!("Queued {queued} install(s); keep browsing meanwhile."),
+            LogLevel::Info,
+        );
+    }
+}
+
+fn push_install(app: &mut App, target: AppEntry, cmd: String) {
+    let post_cmd = target
+        .post_install
+        .as_ref()
+        .and_then(|commands| entry_command(&target, commands, app.platform));
+    let env = command_env(&target, &app.command_env);
+    app.install_queue.push(InstallJob {
+        entry: target,
+        kind: JobKind::Install,
+        cmd,
+        post_cmd,
+        env,
+        platform: app.platform,
+    });
+}
+
+/// Logs what the install worker finished since the last frame and updates the cached state.
+fn apply_queue_events(app: &mut App) {
+    let mut changed = false;
+    for event in app.install_queue.poll() {
+        let QueueEvent::Finished {
+            entry,
+            kind,
+            result,
+            post_result,
+            retry_notes,
+            took,
+        } = event
+        else {
+            continue;
+        };
+        changed = true;
+        for note in retry_notes {
+            app.log(format!("{}: {note}", entry.name), LogLevel::Info);
+        }
+        let took = format_elapsed(took);
+        let (action, title) = match kind {
+            JobKind::Install => ("install", "Install"),
+            JobKind::Uninstall => ("uninstall", "Uninstall"),
+        };
+        match result {
+            Ok(()) if kind == JobKind::Uninstall => {
+                app.mark_uninstalled(&entry.id);
+                app.log(
+                    format!("Uninstalled {} in {took}", entry.name),
+                    LogLevel::Success,
+                );
+                app.last_uninstalled
+                    .retain(|removed| removed.id != entry.id);
+                app.last_uninstalled.push(*entry);
+            }
+            Ok(()) => {
+                settle_install(app, &entry, &took);
+                if let Some(Err(e)) = post_result {
+                    let hint =
+                        output_hint(app.record_failure(format!("Post-install {}", entry.name), &e));
+                    app.log(
+                        format!("Post-install for {} failed: {e}{hint}", entry.name),
+                        LogLevel::Error,
+                    );
+                }
+            }
+            Err(e) if is_cancelled(&e) => {
+                app.log(
+                    format!("Cancelled {action} of {}", entry.name),
+                    LogLevel::Info,
+                );
+            }
+            Err(e) => {
+                let hint = output_hint(app.record_failure(format!("{title} {}", entry.name), &e));
+                app.log(
+                    format!("{title} failed for {}: {e}{hint}", entry.name),
+                    LogLevel::Error,
+                );
+            }
+        }
+    }
+    if changed {
+        refresh_filter(app);
+    }
+}
+
+/// Reports a finished `V` check and re-filters, since the Outdated tab depends on it.
+fn apply_version_check(app: &mut App) {
+    let Some(outdated) = app.finish_version_check() else {
+        return;
+    };
+    match outdated {
+        0 => app.set_status("Everything with a known version is up to date."),
+        1 => app.set_status("1 app is outdated."),
+        n => app.set_status(format!("{n} apps are outdated.")),
+    }
+    refresh_filter(app);
+}
+
+/// Re-probes an entry whose install command exited 0 and logs what it found. Returns a
+/// warning for the status line when the entry still isn't installed, which usually means a
+/// broken catalog entry.
+fn settle_install(app: &mut App, target: &AppEntry, took: &str) -> Option<String> {
+    app.last_uninstalled.retain(|entry| entry.id != target.id);
+    let warning = match app.reprobe_installed(&target.id) {
+        InstallState::Installed => {
+            app.log(
+                format!("Installed {} in {took}", target.name),
+                LogLevel::Success,
+            );
+            return None;
+        }
+        InstallState::Missing => format!("{} still not found on PATH", target.binary),
+        InstallState::Conflict => format!("its verify command rejects {}", target.binary),
+    };
+    app.log(
+        format!(
+            "{}: install command exited 0 in {took}, but {warning}",
+            target.name
+        ),
+        LogLevel::Error,
+    );
+    Some(warning)
+}
+
+fn outcome(target: &AppEntry, ok: bool, detail: impl Into<String>) -> BatchOutcome {
+    BatchOutcome {
+        name: target.name.clone(),
+        ok,
+        detail: detail.into(),
+    }
+}
+
+fn entry_names(targets: &[AppEntry]) -> String {
+    targets
+        .iter()
+        .map(|t| t.name.clone())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn output_hint(captured: bool) -> &'static str {
+    if captured {
+        ". Press E to see its output."
+    } else {
+        ""
+    }
+}
+
+fn uninstall_entries(
+    app: &mut App,
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    targets: Vec<AppEntry>,
+) {
+    let mut removed = Vec::new();
+    let mut outcomes = Vec::new();
+    let total = targets.len();
+    let batch = total > 1;
+    for (index, target) in targets.into_iter().enumerate() {
+        if app.install_queue.contains(&target.id) {
+            app.log(
+                format!("{} is in the queue; wait for it", target.name),
+                LogLevel::Info,
+            );
+            outcomes.push(outcome(&target, false, "in the queue"));
+            continue;
+        }
+        let uninstall_cmd =
+            match decide_uninstall(&target, app.platform, app.install_state(&target)) {
+                ActionOutcome::Run(cmd) => cmd,
+                ActionOutcome::NotInstalled => {
+                    outcomes.push(outcome(&target, true, "not installed"));
+                    continue;
+                }
+                _ => {
+                    outcomes.push(outcome(
+                        &target,
+                        false,
+                        format!("no uninstall command on {}", app.platform.label()),
+                    ));
+                    continue;
+                }
+            };
+        if !target.is_interactive() {
+            let queued = format!("Queued {}; it uninstalls in the background.", target.name);
+            app.set_status(queued.clone());
+            app.log(queued, LogLevel::Info);
+            let env = command_env(&target, &app.command_env);
+            app.install_queue.push(InstallJob {
+                entry: target.clone(),
+                kind: JobKind::Uninstall,
+                cmd: uninstall_cmd,
+                post_cmd: None,
+                env,
+                platform: app.platform,
+            });
+            outcomes.push(outcome(&target, true, "queued"));
+            continue;
+        }
+        app.set_status(format!(
+            "Uninstalling {} using: {}",
+            target.name, uninstall_cmd
+        ));
+
+        let message = format!(
+            "About to run uninstall command for {}.\n\nCommand:\n{}\n\nIf sudo asks for password, type normally. Press Ctrl-C to cancel.",
+            target.name, uninstall_cmd
+        );
+
+        let env = command_env(&target, &app.command_env);
+        let started = Instant::now();
+        let result = suspend_tui_for_command(terminal, &message, app.pause_after_command, || {
+            run_install_cmd(&uninstall_cmd, app.platform, install_timeout(), &env)
+        });
+
+        let took = format_elapsed(started.elapsed());
+
+        match result {
+            Ok(_) => {
+                app.mark_uninstalled(&target.id);
+                app.log(
+                    format!("Uninstalled {} in {took}", target.name),
+                    LogLevel::Success,
+                );
+                app.set_status_level(
+                    format!(
+                        "Uninstalled {} successfully in {took}. Ctrl+Z reinstalls it.",
+                        target.name
+                    ),
+                    LogLevel::Success,
+                );
+                outcomes.push(outcome(&target, true, format!("uninstalled in {took}")));
+                removed.push(target);
+            }
+            Err(e) => {
+                app.log(format!("Error: {}", e), LogLevel::Error);
+                let hint =
+                    output_hint(app.record_failure(format!("Uninstall {}", target.name), &e));
+                app.set_status_level(
+                    format!("Uninstall failed for {}: {}{hint}", target.name, e),
+                    LogLevel::Error,
+                );
+                outcomes.push(outcome(&target, false, e.to_string()));
+                if is_cancelled(&e) {
+                    log_batch_stopped(app, total - index - 1);
+                    break;
+                }
+            }
+        }
+    }
+    if !removed.is_empty() {
+        app.last_uninstalled = removed;
+    }
+    if batch {
+        app.batch_report = Some(BatchReport {
+            title: "Uninstall results".to_string(),
+            outcomes,
+            tally_words: ("succeeded", "failed"),
+        });
+    }
+    refresh_filter(app);
+}
+
+/// Ctrl-C cancels the whole batch, not just the app whose command was running.
+fn log_batch_stopped(app: &mut App, skipped: usize) {
+    if skipped > 0 {
+        app.log(
+            format!("Cancelled; skipped the remaining {skipped} app(s)"),
+            LogLevel::Info,
+        );
+    }
+}
+
+/// Inserts a bracketed paste in one go. The search box is a single line, so line breaks
+/// become spaces.
+fn paste_into_search(app: &mut App, text: &str) {
+    let text: String = text
+        .trim_end_matches(['\r', '\n'])
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    if text.is_empty() {
+        return;
+    }
+    app.search_input.push_str(&text);
+    app.history_cursor = None;
+    refresh_filter(app);
+}
+
+fn undo_uninstall(app: &mut App) {
+    if app.last_uninstalled.is_empty() {
+        app.set_status("Nothing to undo.");
+        return;
+    }
+    let targets = app.last_uninstalled.clone();
+    app.ask_confirm(
+        "Undo Uninstall",
+        format!("Reinstall:\n{}?", entry_names(&targets)),
+        ConfirmAction::Install(targets),
+    );
+    app.set_status("Press Enter to reinstall, Esc to cancel.");
+}
+
+/// Clicks pick tabs, categories and list rows; the wheel moves the selection. Ignored while
+/// any popup is open, since popups are driven by keys only.
+fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    let popup_open = app.batch_report.is_some()
+        || app.reload_diff.is_some()
+        || app.search_mode
+        || app.help_mode
+        || app.stats_mode
+        || app.output_mode
+        || app.tag_mode
+        || app.confirm_dialog.is_some();
+    if popup_open {
+        return;
+    }
+    let hit = |area: &Rect| area.contains(Position::new(mouse.column, mouse.row));
+    match mouse.kind {
+        MouseEventKind::ScrollDown => app.move_down(),
+        MouseEventKind::ScrollUp => app.move_up(),
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(tab) = app.click_areas.tabs.iter().position(hit) {
+                select_tab(app, tab);
+            } else if let Some(category) = app.click_areas.categories.iter().position(hit) {
+                if category != app.selected_category {
+                    app.selected_category = category;
+                    refresh_filter(app);
+                }
+            } else if hit(&app.click_areas.list) {
+                let row = app.list_state.offset() + usize::from(mouse.row - app.click_areas.list.y);
+                if app.list_rows.get(row).is_some_and(ListRow::is_selectable) {
+                    app.list_state.select(Some(row));
+                    app.detail_scroll = 0;
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 pub fn run(app: &mut App, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
     loop {
+        apply_queue_events(app);
+        apply_version_check(app);
         terminal.draw(|frame| ui(frame, app))?;
 
-        if !event::poll(Duration::from_millis(100))? {
+        if take_interrupt() {
+            break;
+        }
+
+        if app
+            .catalog_watcher
+            .as_ref()
+            .is_some_and(|watcher| watcher.changed())
+        {
+            reload_catalog(app);
             continue;
         }
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Press {
+        if !event::poll(app.next_poll())? {
+            continue;
+        }
+
+        let key = match event::read()? {
+            Event::Key(key) => key,
+            Event::Resize(_, _) => {
+                // Drop the previous buffer so the next draw repaints every cell.
+                terminal.autoresize()?;
+                terminal.clear()?;
                 continue;
             }
-
-            if app.search_mode {
-                match key.code {
-                    KeyCode::Esc => {
-                        app.search_mode = false;
-                    }
-                    KeyCode::Enter => {
-                        app.search_mode = false;
-                        app.set_status(format!("Search applied: '{}'", app.search_input));
-                    }
-                    KeyCode::Backspace => {
-                        app.search_input.pop();
-                        refresh_filter(app);
-                    }
-                    KeyCode::Char(c) => {
-                        if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                            app.search_input.push(c);
-                            refresh_filter(app);
-                        }
-                    }
-                    _ => {}
+            Event::Paste(text) => {
+                if app.search_mode {
+                    paste_into_search(app, &text);
                 }
                 continue;
             }
+            Event::Mouse(mouse) => {
+                handle_mouse(app, mouse);
+                continue;
+            }
+            _ => continue,
+        };
 
-            if app.confirm_mode {
-                match key.code {
-                    KeyCode::Enter => {
-                        if app.confirm_selected {
-                            if let Some(ConfirmAction::Uninstall(targets)) =
-                                app.confirm_action.clone()
-                            {
-                                app.confirm_mode = false;
-                                app.confirm_action = None;
-
-                                for target in targets {
-                                    let uninstall_cmd =
-                                        match command_for_platform(&target.uninstall, app.platform)
-                                        {
-                                            Some(cmd) => cmd.to_string(),
-                                            None => continue,
-                                        };
-                                    app.set_status(format!(
-                                        "Uninstalling {} using: {}",
-                                        target.name, uninstall_cmd
-                                    ));
-
-                                    let message = format!(
-                                        "About to run uninstall command for {}.\n\nCommand:\n{}\n\nIf sudo asks for password, type normally.",
-                                        target.name, uninstall_cmd
-                                    );
-
-                                    let result =
-                                        suspend_tui_for_command(terminal, &message, || {
-                                            run_install_cmd(&uninstall_cmd, app.platform)
-                                        });
-
-                                    match result {
-                                        Ok(_) => {
-                                            app.log(
-                                                format!("Uninstalled {}", target.name),
-                                                LogLevel::Success,
-                                            );
-                                            app.set_status(format!(
-                                                "Uninstalled {} successfully.",
-                                                target.name
-                                            ))
-                                        }
-                                        Err(e) => {
-                                            app.log(format!("Error: {}", e), LogLevel::Error);
-                                            app.set_status(format!(
-                                                "Uninstall failed for {}: {}",
-                                                target.name, e
-                                            ))
-                                        }
-                                    }
-                                }
-                                app.refresh_installed_cache();
-                                refresh_filter(app);
-                            }
-                        } else {
-                            app.confirm_mode = false;
-                            app.confirm_action = None;
-                            app.set_status("Uninstall cancelled.");
-                        }
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if app.batch_report.is_some() {
+            app.batch_report = None;
+            continue;
+        }
+
+        if app.reload_diff.is_some() {
+            app.reload_diff = None;
+            continue;
+        }
+
+        if app.search_mode {
+            match key.code {
+                KeyCode::Esc => {
+                    app.search_mode = false;
+                    app.history_cursor = None;
+                }
+                KeyCode::Enter => {
+                    app.search_mode = false;
+                    app.push_search_history();
+                    app.set_status(format!("Search applied: '{}'", app.search_input));
+                }
+                KeyCode::Up | KeyCode::Down if app.recall_search(key.code == KeyCode::Up) => {
+                    refresh_filter(app);
+                }
+                KeyCode::Backspace => {
+                    app.search_input.pop();
+                    refresh_filter(app);
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    app.search_input.push(c);
+                    refresh_filter(app);
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if app.help_mode {
+            if matches!(
+                key.code,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('?') | KeyCode::Char('q')
+            ) {
+                app.help_mode = false;
+            }
+            continue;
+        }
+
+        if app.stats_mode {
+            if matches!(
+                key.code,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('d') | KeyCode::Char('q')
+            ) {
+                app.stats_mode = false;
+            }
+            continue;
+        }
+
+        if app.output_mode {
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('q') => {
+                    app.output_mode = false;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app.output_scroll = app.output_scroll.saturating_add(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app.output_scroll = app.output_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => app.output_scroll = app.output_scroll.saturating_add(10),
+                KeyCode::PageUp => app.output_scroll = app.output_scroll.saturating_sub(10),
+                _ => {}
+            }
+            continue;
+        }
+
+        if app.tag_mode {
+            match key.code {
+                KeyCode::Esc
+                | KeyCode::Enter
+                | KeyCode::Char('q')
+                | KeyCode::Char('t')
+                | KeyCode::Char('T') => {
+                    app.tag_mode = false;
+                }
+                KeyCode::Down | KeyCode::Char('j') if !app.tags.is_empty() => {
+                    app.tag_cursor = (app.tag_cursor + 1) % app.tags.len();
+                }
+                KeyCode::Up | KeyCode::Char('k') if !app.tags.is_empty() => {
+                    app.tag_cursor = app.tag_cursor.checked_sub(1).unwrap_or(app.tags.len() - 1);
+                }
+                KeyCode::Char(' ') => {
+                    app.toggle_tag_at_cursor();
+                    refresh_filter(app);
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    app.tag_match_all = !app.tag_match_all;
+                    refresh_filter(app);
+                }
+                KeyCode::Char('c') | KeyCode::Char('C') => {
+                    app.active_tags.clear();
+                    refresh_filter(app);
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if let Some(dialog) = app.confirm_dialog.take() {
+            match key.code {
+                KeyCode::Enter => match dialog.action {
+                    ConfirmAction::Launch(_, existing) if !app.confirm_selected => {
+                        focus_existing(app, &existing);
                     }
-                    KeyCode::Left | KeyCode::Char('h') => {
-                        app.confirm_selected = true;
+                    action if !app.confirm_selected => {
+                        app.set_status(format!("{} cancelled.", action.label()));
                     }
-                    KeyCode::Right | KeyCode::Char('l') => {
-                        app.confirm_selected = false;
+                    ConfirmAction::Install(targets) => {
+                        install_entries(app, terminal, targets);
                     }
-                    KeyCode::Esc | KeyCode::Char('q') => {
-                        app.confirm_mode = false;
-                        app.confirm_action = None;
-                        app.set_status("Uninstall cancelled.");
+                    ConfirmAction::Uninstall(targets) => {
+                        uninstall_entries(app, terminal, targets);
                     }
-                    _ => {}
+                    ConfirmAction::Launch(target, _) => launch_separately(app, &target),
+                    ConfirmAction::LaunchMany(targets) => launch_many(app, &targets),
+                    ConfirmAction::Quit => break,
+                },
+                KeyCode::Left | KeyCode::Char('h') => {
+                    app.confirm_selected = true;
+                    app.confirm_dialog = Some(dialog);
                 }
-                continue;
+                KeyCode::Right | KeyCode::Char('l') => {
+                    app.confirm_selected = false;
+                    app.confirm_dialog = Some(dialog);
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.set_status(format!("{} cancelled.", dialog.action.label()));
+                }
+                _ => app.confirm_dialog = Some(dialog),
             }
+            continue;
+        }
 
-            match key.code {
-                KeyCode::Char('q') => break,
-                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
-                KeyCode::Down | KeyCode::Char('j') => app.move_down(),
-                KeyCode::Up | KeyCode::Char('k') => app.move_up(),
-                KeyCode::Tab => cycle_tab_right(app),
-                KeyCode::BackTab => cycle_tab_left(app),
-                KeyCode::Left => category_left(app),
-                KeyCode::Right => category_right(app),
-                KeyCode::Char(' ') => app.toggle_selected_current(),
-                KeyCode::Char('/') => {
-                    app.search_mode = true;
+        // Every modal above swallows `q` to close itself, so this only quits from the list.
+        match key.code {
+            KeyCode::Char('q') if app.install_queue.is_busy() => {
+                app.ask_confirm(
+                    "Quit",
+                    format!(
+                        "{} queued job(s) have not finished. Quit anyway?",
+                        app.install_queue.pending()
+                    ),
+                    ConfirmAction::Quit,
+                );
+            }
+            KeyCode::Char('q') if app.confirm_quit => {
+                app.ask_confirm("Quit", "Quit TUIHub?", ConfirmAction::Quit);
+            }
+            KeyCode::Char('q') => break,
+            KeyCode::Char('c')
+                if key.modifiers.contains(KeyModifiers::CONTROL) && app.install_queue.cancel() =>
+            {
+                app.set_status("Cancelling queued jobs.");
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                undo_uninstall(app)
+            }
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                edit_catalog(app, terminal)
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let targets = app.selected_entries();
+                if targets.is_empty() {
+                    app.log("No app selected to check.".to_string(), LogLevel::Info);
+                } else {
+                    preflight(app, targets);
                 }
-                KeyCode::Esc => {
-                    if !app.search_input.is_empty() {
-                        app.search_input.clear();
-                        refresh_filter(app);
-                        app.set_status("Search cleared.");
-                    }
+            }
+            KeyCode::Down | KeyCode::Char('j') => app.move_down(),
+            KeyCode::Up | KeyCode::Char('k') => app.move_up(),
+            KeyCode::PageDown => app.scroll_detail_down(5),
+            KeyCode::PageUp => app.scroll_detail_up(5),
+            KeyCode::Tab => cycle_tab_right(app),
+            KeyCode::BackTab => cycle_tab_left(app),
+            KeyCode::Char(digit @ '1'..='9') => select_tab(app, digit as usize - '1' as usize),
+            KeyCode::Left => category_left(app),
+            KeyCode::Right => category_right(app),
+            KeyCode::Char(' ') => app.toggle_selected_current(),
+            KeyCode::Char('/') => {
+                app.search_mode = true;
+            }
+            KeyCode::Char('?') => {
+                app.help_mode = true;
+            }
+            KeyCode::Char('d') | KeyCode::Char('D') => {
+                app.stats_mode = true;
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                if app.failure_report.is_some() {
+                    app.output_mode = true;
+                    // Start at the bottom, where the actual error usually is.
+                    app.output_scroll = u16::MAX;
+                } else {
+                    app.set_status("No failed command output to show.");
                 }
-                KeyCode::Char('c') | KeyCode::Char('C') => app.clear_selection(),
-                KeyCode::Enter | KeyCode::Char('\r') => {
-                    let idx = match app.list_state.selected() {
-                        Some(i) => i,
-                        None => {
-                            app.set_status("No app focused to launch.");
-                            continue;
-                        }
-                    };
-                    let entry_idx = match app.filtered_indices.get(idx) {
-                        Some(&idx) => idx,
-                        None => {
-                            app.set_status("No app focused to launch.");
-                            continue;
-                        }
-                    };
-                    let target = match app.entries.get(entry_idx) {
-                        Some(entry) => entry,
-                        None => {
-                            app.set_status("No app focused to launch.");
-                            continue;
-                        }
-                    };
+            }
+            KeyCode::Esc if !app.search_input.is_empty() => {
+                app.search_input.clear();
+                refresh_filter(app);
+                app.set_status("Search cleared.");
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => app.clear_selection(),
+            KeyCode::Char('f') => toggle_favorite(app),
+            KeyCode::Char('F') => toggle_favorites_only(app),
+            KeyCode::Char('H') => toggle_show_hidden(app),
+            KeyCode::Char('n') | KeyCode::Char('N') => mark_new_seen(app),
+            KeyCode::Char('s') | KeyCode::Char('S') => cycle_sort(app),
+            KeyCode::Char('A') => toggle_hide_unavailable(app),
+            KeyCode::Char('m') | KeyCode::Char('M') => cycle_launch_mode(app),
+            KeyCode::Char('y') | KeyCode::Char('Y') => copy_attach_command(app),
+            KeyCode::Char('b') | KeyCode::Char('B') => toggle_pinned_detail(app),
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                app.show_all_commands = !app.show_all_commands;
+                app.detail_scroll = 0;
+            }
+            KeyCode::Char('[') => app.detail_focus = app.detail_focus.cycle(-1),
+            KeyCode::Char(']') => app.detail_focus = app.detail_focus.cycle(1),
+            KeyCode::Char('=') => {
+                let section = app.detail_focus;
+                if !app.collapsed_sections.remove(&section) {
+                    app.collapsed_sections.insert(section);
+                }
+                app.detail_scroll = 0;
+            }
+            KeyCode::Char('w') => reveal_binary(app, false),
+            KeyCode::Char('W') => reveal_binary(app, true),
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                if app.check_updates() {
+                    app.set_status("Checking installed versions...");
+                } else {
+                    app.set_status("Still checking installed versions...");
+                }
+            }
+            KeyCode::Char('g') | KeyCode::Char('G') => toggle_grouping(app),
+            KeyCode::Char('z') | KeyCode::Char('Z') => toggle_collapse(app),
+            KeyCode::Char('r') | KeyCode::Char('R') => reload_catalog(app),
+            KeyCode::Char('p') | KeyCode::Char('P') => cycle_theme(app),
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                if app.tags.is_empty() {
+                    app.set_status("No tags defined in the catalog.");
+                } else {
+                    app.tag_mode = true;
+                }
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                let Some(target) = app.current_entry().cloned() else {
+                    app.set_status("No app focused to open.");
+                    continue;
+                };
 
-                    let target_name = target.name.clone();
+                let link = target.link();
+                let kind = if target.homepage.is_some() {
+                    "Homepage"
+                } else {
+                    "Repo"
+                };
+                if !looks_like_url(link) {
+                    app.set_status_level(
+                        format!("{kind} for {} is not a valid URL: {link}", target.name),
+                        LogLevel::Error,
+                    );
+                    continue;
+                }
 
-                    if !has_tmux() {
-                        app.set_status(format!(
-                            "tmux is required for launch. {}",
-                            tmux_install_hint(app.platform)
-                        ));
+                match open_url(link, app.platform) {
+                    Ok(()) => {
+                        app.log(format!("Opened {link}"), LogLevel::Info);
+                        app.set_status_level(
+                            format!(
+                                "Opened {} for {} in browser.",
+                                kind.to_ascii_lowercase(),
+                                target.name
+                            ),
+                            LogLevel::Success,
+                        )
+                    }
+                    Err(e) => {
+                        app.log(format!("Error: {}", e), LogLevel::Error);
+                        app.set_status_level(
+                            format!("Open failed for {}: {}", target.name, e),
+                            LogLevel::Error,
+                        )
+                    }
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('\r') => {
+                if app.is_grouped() && app.focused_index().is_none() {
+                    toggle_collapse(app);
+                    continue;
+                }
+                let entry_idx = match app.focused_index() {
+                    Some(idx) => idx,
+                    None => {
+                        app.set_status("No app focused to launch.");
                         continue;
                     }
-
-                    if !app.is_installed(&target) {
-                        app.set_status(format!(
-                            "{} is not installed. Press I to install.",
-                            target_name
-                        ));
+                };
+                let target = match app.entries.get(entry_idx) {
+                    Some(entry) => entry,
+                    None => {
+                        app.set_status("No app focused to launch.");
                         continue;
                     }
+                };
 
-                    match launch_in_tmux(&target) {
-                        Ok(target_loc) => {
-                            if let Some(session_name) = target_loc.strip_prefix("session:") {
-                                app.log(
-                                    format!("Session '{}' opened", session_name),
-                                    LogLevel::Info,
-                                );
-                                app.set_status(format!(
-                                    "Launched {} in tmux session '{}'. Attach: tmux attach -t {}",
-                                    target_name, session_name, session_name
-                                ));
-                            } else if let Some(window_name) = target_loc.strip_prefix("window:") {
-                                app.log(format!("Window '{}' opened", window_name), LogLevel::Info);
-                                app.set_status(format!(
-                                    "Launched {} in tmux window '{}'.",
-                                    target_name, window_name
-                                ));
-                            } else {
-                                app.log(format!("Launched {}", target_name), LogLevel::Info);
-                                app.set_status(format!("Launched {} in tmux.", target_name));
-                            }
-                        }
-                        Err(e) => {
-                            app.log(format!("Error: {}", e), LogLevel::Error);
-                            app.set_status(format!("Launch failed for {}: {}", target_name, e))
-                        }
-                    }
+                let target_name = target.name.clone();
+
+                if !has_tmux() {
+                    app.set_status_level(
+                        format!(
+                            "tmux is required for launch. {}",
+                            tmux_install_hint(app.platform)
+                        ),
+                        LogLevel::Error,
+                    );
+                    continue;
                 }
-                KeyCode::Char('i') | KeyCode::Char('I') => {
-                    let targets = app.selected_entries();
-                    if targets.is_empty() {
-                        app.set_status("No app selected to install.");
+
+                match decide_launch(target, app.install_state(target)) {
+                    ActionOutcome::Run(_) => {}
+                    ActionOutcome::Conflict => {
+                        app.set_status_level(
+                            format!(
+                                "'{}' on PATH is not {}. Refusing to launch the wrong program.",
+                                target.binary, target_name
+                            ),
+                            LogLevel::Error,
+                        );
                         continue;
                     }
-
-                    if app.platform == Platform::Unknown {
-                        app.set_status("Unknown platform. Cannot install.");
+                    _ => {
+                        app.set_status_level(
+                            format!("{} is not installed. Press I to install.", target_name),
+                            LogLevel::Error,
+                        );
                         continue;
                     }
+                }
 
-                    for target in targets {
-                        if app.is_installed(&target) {
-                            app.set_status(format!("{} already installed", target.name));
-                            app.log(format!("{} already installed", target.name), LogLevel::Info);
-                            continue;
-                        }
+                let target = target.clone();
+                launch_or_confirm(app, &target);
+            }
+            KeyCode::Char('I') => {
+                let targets = app.selected_entries();
+                if targets.is_empty() {
+                    app.log("No app selected to queue.".to_string(), LogLevel::Info);
+                } else if app.platform_overridden {
+                    app.log(
+                        "Platform is overridden; use i to install with a confirmation.".to_string(),
+                        LogLevel::Error,
+                    );
+                } else {
+                    queue_installs(app, targets);
+                }
+            }
+            KeyCode::Char('i') => {
+                let targets = app.selected_entries();
+                if targets.is_empty() {
+                    app.set_status("No app selected to install.");
+                    continue;
+                }
 
-                        let install_cmd = match command_for_platform(&target.install, app.platform)
-                        {
-                            Some(cmd) if !cmd.trim().is_empty() => cmd.to_string(),
-                            _ => {
-                                app.set_status(format!(
-                                    "No install command defined for {} on {}.",
-                                    target.name,
-                                    app.platform.label()
-                                ));
-                                continue;
-                            }
-                        };
-                        app.set_status(format!(
-                            "Installing {} using: {}",
-                            target.name, install_cmd
-                        ));
+                if app.platform == Platform::Unknown {
+                    app.set_status_level("Unknown platform. Cannot install.", LogLevel::Error);
+                    continue;
+                }
 
-                        let message = format!(
-                            "About to run install command for {}.\n\nCommand:\n{}\n\nIf sudo asks for password, type normally.",
-                            target.name, install_cmd
-                        );
+                if app.platform_overridden {
+                    app.ask_confirm(
+                        "Confirm Install",
+                        format!(
+                            "Platform is overridden to {}. Really install:\n{}?",
+                            app.platform.label(),
+                            entry_names(&targets)
+                        ),
+                        ConfirmAction::Install(targets),
+                    );
+                    app.set_status(format!(
+                        "Platform is overridden to {}. Press Enter to confirm install, Esc to cancel.",
+                        app.platform.label()
+                    ));
+                    continue;
+                }
 
-                        let result = suspend_tui_for_command(terminal, &message, || {
-                            run_install_cmd(&install_cmd, app.platform)
-                        });
+                install_entries(app, terminal, targets);
+            }
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                let targets = app.selected_entries();
+                if targets.is_empty() {
+                    app.set_status("No app selected to uninstall.");
+                    continue;
+                }
 
-                        match result {
-                            Ok(_) => {
-                                app.log(format!("Installed {}", target.name), LogLevel::Success);
-                                app.set_status(format!("Installed {} successfully.", target.name))
-                            }
-                            Err(e) => {
-                                app.log(format!("Error: {}", e), LogLevel::Error);
-                                app.set_status(format!("Install failed for {}: {}", target.name, e))
-                            }
-                        }
-                    }
-                    app.refresh_installed_cache();
-                    refresh_filter(app);
+                if app.platform == Platform::Unknown {
+                    app.set_status_level("Unknown platform. Cannot uninstall.", LogLevel::Error);
+                    continue;
                 }
-                KeyCode::Char('u') | KeyCode::Char('U') => {
-                    let targets = app.selected_entries();
-                    if targets.is_empty() {
-                        app.set_status("No app selected to uninstall.");
-                        continue;
-                    }
 
-                    if app.platform == Platform::Unknown {
-                        app.set_status("Unknown platform. Cannot uninstall.");
-                        continue;
-                    }
+                let installed_targets: Vec<_> = targets
+                    .iter()
+                    .filter(|target| {
+                        matches!(
+                            decide_uninstall(target, app.platform, app.install_state(target)),
+                            ActionOutcome::Run(_)
+                        )
+                    })
+                    .cloned()
+                    .collect();
 
-                    let installed_targets: Vec<_> = targets
+                if installed_targets.is_empty() {
+                    let not_installed: Vec<_> = targets
                         .iter()
-                        .filter(|target| app.is_installed(target))
-                        .filter(|target| {
-                            if let Some(cmd) = command_for_platform(&target.uninstall, app.platform)
-                            {
-                                !cmd.trim().is_empty()
-                            } else {
-                                false
-                            }
-                        })
-                        .cloned()
+                        .filter(|t| !app.is_installed(t))
+                        .map(|t| t.name.clone())
                         .collect();
-
-                    if installed_targets.is_empty() {
-                        let not_installed: Vec<_> = targets
-                            .iter()
-                            .filter(|t| !app.is_installed(t))
-                            .map(|t| t.name.clone())
-                            .collect();
-                        if !not_installed.is_empty() {
-                            app.set_status(format!("{} not installed.", not_installed.join(", ")));
-                            app.log(
-                                format!("{} not installed", not_installed.join(", ")),
-                                LogLevel::Info,
-                            );
-                        } else {
-                            app.set_status(
-                                "No uninstall command defined for selected apps on this platform.",
-                            );
-                        }
-                        continue;
+                    if !not_installed.is_empty() {
+                        app.set_status(format!("{} not installed.", not_installed.join(", ")));
+                        app.log(
+                            format!("{} not installed", not_installed.join(", ")),
+                            LogLevel::Info,
+                        );
+                    } else {
+                        app.set_status_level(
+                            "No uninstall command defined for selected apps on this platform.",
+                            LogLevel::Error,
+                        );
                     }
+                    continue;
+                }
 
-                    app.confirm_mode = true;
-                    app.confirm_selected = true;
-                    app.confirm_action = Some(ConfirmAction::Uninstall(installed_targets));
-                    app.set_status("Press Enter to confirm uninstall, Esc to cancel.");
-                }
-                KeyCode::Char('l') | KeyCode::Char('L') => {
-                    let targets: Vec<AppEntry> = if app.selected_ids.is_empty() {
-                        match app.list_state.selected() {
-                            Some(idx) => app
-                                .filtered_indices
-                                .get(idx)
-                                .and_then(|&entry_idx| app.entries.get(entry_idx))
-                                .cloned()
-                                .into_iter()
-                                .collect(),
-                            None => vec![],
-                        }
-                    } else {
-                        app.selected_entries()
-                    };
+                app.ask_confirm(
+                    "Confirm Uninstall",
+                    format!(
+                        "Are you sure you want to uninstall:\n{}?",
+                        entry_names(&installed_targets)
+                    ),
+                    ConfirmAction::Uninstall(installed_targets),
+                );
+                app.set_status("Press Enter to confirm uninstall, Esc to cancel.");
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                let targets: Vec<AppEntry> = if app.selected_ids.is_empty() {
+                    app.current_entry().cloned().into_iter().collect()
+                } else {
+                    app.selected_entries()
+                };
 
-                    if targets.is_empty() {
-                        app.set_status("No app selected or focused to launch.");
-                        continue;
-                    }
+                if targets.is_empty() {
+                    app.set_status("No app selected or focused to launch.");
+                    continue;
+                }
 
-                    if !has_tmux() {
-                        app.set_status(format!(
+                if !has_tmux() {
+                    app.set_status_level(
+                        format!(
                             "tmux is required for launch. {}",
                             tmux_install_hint(app.platform)
-                        ));
-                        continue;
-                    }
-
-                    for target in targets {
-                        let target_name = target.name.clone();
-                        if !app.is_installed(&target) {
-                            app.set_status(format!(
-                                "{} is not installed yet. Install first.",
-                                target_name
-                            ));
-                            app.log(format!("{} not installed", target_name), LogLevel::Info);
-                            continue;
-                        }
+                        ),
+                        LogLevel::Error,
+                    );
+                    continue;
+                }
 
-                        match launch_in_tmux(&target) {
-                            Ok(target_loc) => {
-                                if let Some(session_name) = target_loc.strip_prefix("session:") {
-                                    app.log(
-                                        format!("Session '{}' opened", session_name),
-                                        LogLevel::Info,
-                                    );
-                                    app.set_status(format!(
-                                        "Launched {} in tmux session '{}'. Attach: tmux attach -t {}",
-                                        target_name, session_name, session_name
-                                    ));
-                                } else if let Some(window_name) = target_loc.strip_prefix("window:")
-                                {
-                                    app.log(
-                                        format!("Window '{}' opened", window_name),
-                                        LogLevel::Info,
-                                    );
-                                    app.set_status(format!(
-                                        "Launched {} in tmux window '{}'.",
-                                        target_name, window_name
-                                    ));
-                                } else {
-                                    app.log(format!("Launched {}", target_name), LogLevel::Info);
-                                    app.set_status(format!("Launched {} in tmux.", target_name));
-                                }
+                let launchable: Vec<AppEntry> = targets
+                    .into_iter()
+                    .filter(
+                        |target| match decide_launch(target, app.install_state(target)) {
+                            ActionOutcome::Conflict => {
+                                app.set_status_level(
+                                    format!(
+                                    "'{}' on PATH is not {}. Refusing to launch the wrong program.",
+                                    target.binary, target.name
+                                ),
+                                    LogLevel::Error,
+                                );
+                                app.log(format!("{} conflict", target.name), LogLevel::Error);
+                                false
                             }
-                            Err(e) => {
-                                app.log(format!("Error: {}", e), LogLevel::Error);
-                                app.set_status(format!("Launch failed for {}: {}", target_name, e))
+                            ActionOutcome::Run(_) => true,
+                            _ => {
+                                app.set_status_level(
+                                    format!("{} is not installed yet. Install first.", target.name),
+                                    LogLevel::Error,
+                                );
+                                app.log(format!("{} not installed", target.name), LogLevel::Info);
+                                false
                             }
-                        }
+                        },
+                    )
+                    .collect();
+
+                let limits = app.launch_limits;
+                match launchable.len() {
+                    0 => {}
+                    1 => launch_or_confirm(app, &launchable[0]),
+                    count if count > limits.max => app.log(
+                        format!(
+                            "Refusing to launch {count} apps at once (max_launch = {}).",
+                            limits.max
+                        ),
+                        LogLevel::Error,
+                    ),
+                    count if count > limits.confirm_above => {
+                        let opens = match app.launch_mode {
+                            LaunchMode::Split => format!("one window with {count} panes"),
+                            LaunchMode::Separate => format!("{count} tmux sessions"),
+                        };
+                        app.ask_confirm(
+                            "Confirm Launch",
+                            format!("This opens {opens}:\n{}?", entry_names(&launchable)),
+                            ConfirmAction::LaunchMany(launchable),
+                        );
                     }
+                    _ => launch_many(app, &launchable),
                 }
-                _ => {}
             }
+            _ => {}
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::app::persist::PersistedState;
+    use crate::registry::model::test_entry;
+    use crate::system::exec::run_captured_cmd;
+
+    /// An app that starts from a fresh session, whatever is saved on this machine.
+    fn app_with(entries: Vec<AppEntry>) -> App {
+        App::with_state(entries, "apps.json", PersistedState::default())
+    }
+
+    fn filtered_ids(app: &App) -> Vec<&str> {
+        let mut ids: Vec<&str> = app
+            .filtered_indices
+            .iter()
+            .map(|&index| app.entries[index].id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn install_that_exits_zero_without_its_binary_is_not_marked_installed() {
+        let mut app = app_with(vec![test_entry("ghost", json!({}))]);
+        let target = app.entries[0].clone();
+        let cmd = entry_command(&target, &target.install, app.platform).unwrap();
+        run_captured_cmd(&cmd, app.platform, None, &[]).unwrap();
+
+        let warning = settle_install(&mut app, &target, "0.1s");
+        assert_eq!(
+            warning.as_deref(),
+            Some("tuihub-test-ghost still not found on PATH")
+        );
+        assert_eq!(app.install_state(&target), InstallState::Missing);
+    }
+
+    #[test]
+    fn refresh_filter_leaves_out_hidden_entries_until_shown() {
+        let mut app = app_with(vec![
+            test_entry("shown", json!({})),
+            test_entry("secret", json!({ "hidden": true })),
+        ]);
+
+        refresh_filter(&mut app);
+        assert_eq!(filtered_ids(&app), ["shown"]);
+
+        app.show_hidden = true;
+        refresh_filter(&mut app);
+        assert_eq!(filtered_ids(&app), ["secret", "shown"]);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use crate::registry::load_catalog;
+use crate::registry::model::AppEntry;
+use crate::system::exec::{
+    command_env, detect_install_state, entry_command, install_retries, install_timeout,
+    run_captured_cmd, run_install_cmd, run_install_cmd_with_retries, InstallState,
+};
+use crate::system::os::Platform;
+
+/// A loaded catalog plus everything needed to run its commands, with no TUI attached.
+///
+/// ```no_run
+/// use tuihub::Catalog;
+///
+/// let catalog = Catalog::load("data/apps.json")?.on_retry(|note| eprintln!("{note}"));
+/// for id in catalog.installed_ids() {
+///     println!("{id} is installed");
+/// }
+/// catalog.install("ripgrep")?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct Catalog {
+    entries: Vec<AppEntry>,
+    path: PathBuf,
+    warning: Option<String>,
+    platform: Platform,
+    env: BTreeMap<String, String>,
+    interactive: bool,
+    on_retry: Box<dyn Fn(String)>,
+}
+
+impl Catalog {
+    /// Loads a catalog from a local path or an https URL, for the detected platform.
+    pub fn load(source: &str) -> Result<Self> {
+        let loaded = load_catalog(source)?;
+        Ok(Self {
+            entries: loaded.entries,
+            path: loaded.path,
+            warning: loaded.warning,
+            platform: Platform::detect(),
+            env: BTreeMap::new(),
+            interactive: true,
+            on_retry: Box::new(|_| {}),
+        })
+    }
+
+    /// Picks whose install commands run, instead of the detected platform.
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Extra environment for every command, like the config's `[env]` table.
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Whether commands get this process's terminal, the default, or run with stdin closed
+    /// and their output captured for the error instead of printed.
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Called with a note such as "...; retrying in 2s (attempt 2/3)" before an install is
+    /// retried. Nothing is reported by default.
+    pub fn on_retry(mut self, on_retry: impl Fn(String) + 'static) -> Self {
+        self.on_retry = Box::new(on_retry);
+        self
+    }
+
+    pub fn entries(&self) -> &[AppEntry] {
+        &self.entries
+    }
+
+    /// The local file, or the cached copy of a remote catalog.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Set when a remote catalog could not be fetched and the cache was used instead.
+    pub fn warning(&self) -> Option<&str> {
+        self.warning.as_deref()
+    }
+
+    pub fn platform(&self) -> Platform {
+        self.platform
+    }
+
+    /// Finds an entry by id or alias, ignoring case.
+    ///
+    /// ```no_run
+    /// # let catalog = tuihub::Catalog::load("data/apps.json")?;
+    /// let ripgrep = catalog.get("rg").map(|entry| entry.name.as_str());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn get(&self, id: &str) -> Option<&AppEntry> {
+        self.entries.iter().find(|entry| {
+            entry.id.eq_ignore_ascii_case(id)
+                || entry
+                    .aliases
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(id))
+        })
+    }
+
+    pub fn install_state(&self, id: &str) -> Result<InstallState> {
+        Ok(detect_install_state(self.entry(id)?, self.platform))
+    }
+
+    /// Ids of the entries whose binary is on PATH and passes `verify`.
+    pub fn installed_ids(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|entry| detect_install_state(entry, self.platform) == InstallState::Installed)
+            .map(|entry| entry.id.as_str())
+            .collect()
+    }
+
+    /// Runs the entry's install command, retrying transient failures. Output goes straight to
+    /// this process's stdout/stderr unless [`Catalog::with_interactive`] turned that off. Does
+    /// not run `post_install`; see [`Catalog::post_install`].
+    pub fn install(&self, id: &str) -> Result<()> {
+        let entry = self.entry(id)?;
+        let cmd = entry_command(entry, &entry.install, self.platform).ok_or_else(|| {
+            anyhow!(
+                "no install command defined for {} on {}",
+                entry.name,
+                self.platform.label()
+            )
+        })?;
+        run_install_cmd_with_retries(
+            &cmd,
+            self.platform,
+            install_timeout(),
+            &command_env(entry, &self.env),
+            install_retries(entry),
+            self.interactive,
+            &self.on_retry,
+        )
+    }
+
+    /// Runs the entry's post-install step. Entries without one succeed without doing anything.
+    pub fn post_install(&self, id: &str) -> Result<()> {
+        let entry = self.entry(id)?;
+        let Some(cmd) = entry
+            .post_install
+            .as_ref()
+            .and_then(|commands| entry_command(entry, commands, self.platform))
+        else {
+            return Ok(());
+        };
+        self.run(entry, &cmd)
+    }
+
+    pub fn uninstall(&self, id: &str) -> Result<()> {
+        let entry = self.entry(id)?;
+        let cmd = entry_command(entry, &entry.uninstall, self.platform).ok_or_else(|| {
+            anyhow!(
+                "no uninstall command defined for {} on {}",
+                entry.name,
+                self.platform.label()
+            )
+        })?;
+        self.run(entry, &cmd)
+    }
+
+    fn run(&self, entry: &AppEntry, cmd: &str) -> Result<()> {
+        let env = command_env(entry, &self.env);
+        if self.interactive {
+            run_install_cmd(cmd, self.platform, install_timeout(), &env)
+        } else {
+            run_captured_cmd(cmd, self.platform, install_timeout(), &env)
+        }
+    }
+
+    fn entry(&self, id: &str) -> Result<&AppEntry> {
+        self.get(id)
+            .ok_or_else(|| anyhow!("no app with id '{id}' in the catalog"))
+    }
+}
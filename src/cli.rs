@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+
+use crate::system::os::Platform;
+use crate::ui::theme::ThemePreset;
+
+pub const USAGE: &str = "Usage: tuihub [OPTIONS]
+       tuihub [OPTIONS] install <id|pattern>... [--category <name>]
+       tuihub [OPTIONS] uninstall <id|pattern>... [--category <name>]
+       tuihub [OPTIONS] list [--json]
+       tuihub [OPTIONS] outdated
+       tuihub --print-schema
+       tuihub --check [catalog]
+
+Options:
+  --catalog <path|url> Catalog file, or an https URL cached for offline use
+  --watch              Reload the catalog automatically when it changes on disk
+  --platform <name>    Pretend to run on another platform (preview its commands)
+  --theme <preset>     Color preset: dark, light or high-contrast
+  --no-color           Disable colors (also enabled by the NO_COLOR env var)
+  --ascii              Draw with ASCII only (automatic for TERM=dumb, vt100, ...)
+  --no-icons           Leave out the icon column (also off in ASCII mode)
+  --mouse              Click rows and tabs, scroll with the wheel (blocks text selection)
+  --print-schema       Print the catalog JSON Schema and exit
+  --check              Audit the catalog, print each problem to stderr and exit
+  -h, --help           Show this help
+
+Patterns: 'rip*' globs ids and aliases, 'editor/*' globs category/id.
+--category <name> is short for '<name>/*'.
+
+Exit codes: 0 success, 1 general error, 2 an install/uninstall failed,
+3 catalog could not be loaded, 4 unknown app id, 5 --check found problems";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subcommand {
+    Install(Vec<String>),
+    Uninstall(Vec<String>),
+    List { json: bool },
+    Outdated,
+}
+
+#[derive(Debug, Default)]
+pub struct CliOptions {
+    pub command: Option<Subcommand>,
+    pub catalog: Option<String>,
+    pub watch: bool,
+    pub platform: Option<Platform>,
+    pub theme: Option<ThemePreset>,
+    pub no_color: bool,
+    pub ascii: bool,
+    pub no_icons: bool,
+    pub mouse: bool,
+    pub print_schema: bool,
+    pub check: bool,
+    pub help: bool,
+}
+
+impl CliOptions {
+    pub fn parse() -> Result<Self> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    pub fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = |name: &str| {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .with_context(|| format!("{name} requires a value\n\n{USAGE}"))
+            };
+
+            match flag.as_str() {
+                "--watch" => options.watch = true,
+                "--no-color" => options.no_color = true,
+                "--ascii" => options.ascii = true,
+                "--no-icons" => options.no_icons = true,
+                "--mouse" => options.mouse = true,
+                "--print-schema" => options.print_schema = true,
+                "--check" => options.check = true,
+                "--catalog" => options.catalog = Some(value("--catalog")?),
+                "--platform" => {
+                    let name = value("--platform")?;
+                    let platform = Platform::from_name(&name).with_context(|| {
+                        format!("unknown platform '{name}', expected linux, wsl, mac or windows")
+                    })?;
+                    options.platform = Some(platform);
+                }
+                "--theme" => {
+                    let name = value("--theme")?;
+                    let preset = ThemePreset::from_name(&name).with_context(|| {
+                        format!("unknown theme '{name}', expected dark, light or high-contrast")
+                    })?;
+                    options.theme = Some(preset);
+                }
+                "--category" => {
+                    let category = value("--category")?;
+                    match options.command.as_mut() {
+                        Some(Subcommand::Install(ids)) | Some(Subcommand::Uninstall(ids)) => {
+                            ids.push(format!("{category}/*"))
+                        }
+                        _ => anyhow::bail!(
+                            "--category is only valid after install or uninstall\n\n{USAGE}"
+                        ),
+                    }
+                }
+                "--json" => match options.command.as_mut() {
+                    Some(Subcommand::List { json }) => *json = true,
+                    _ => anyhow::bail!("--json is only valid after list\n\n{USAGE}"),
+                },
+                "-h" | "--help" => options.help = true,
+                other if other.starts_with('-') => {
+                    anyhow::bail!("unknown argument: {other}\n\n{USAGE}")
+                }
+                word => match options.command.as_mut() {
+                    Some(Subcommand::Install(ids)) | Some(Subcommand::Uninstall(ids)) => {
+                        ids.push(word.to_string())
+                    }
+                    Some(Subcommand::List { .. }) => {
+                        anyhow::bail!("unexpected argument for list: {word}\n\n{USAGE}")
+                    }
+                    Some(Subcommand::Outdated) => {
+                        anyhow::bail!("unexpected argument for outdated: {word}\n\n{USAGE}")
+                    }
+                    None if options.check && options.catalog.is_none() => {
+                        options.catalog = Some(word.to_string())
+                    }
+                    None => {
+                        options.command = Some(match word {
+                            "install" => Subcommand::Install(Vec::new()),
+                            "uninstall" => Subcommand::Uninstall(Vec::new()),
+                            "list" => Subcommand::List { json: false },
+                            "outdated" => Subcommand::Outdated,
+                            other => anyhow::bail!("unknown command: {other}\n\n{USAGE}"),
+                        })
+                    }
+                },
+            }
+        }
+
+        if let Some(Subcommand::Install(ids)) | Some(Subcommand::Uninstall(ids)) = &options.command
+        {
+            if ids.is_empty() {
+                anyhow::bail!("expected at least one app id\n\n{USAGE}");
+            }
+        }
+        Ok(options)
+    }
+}
@@ -0,0 +1,351 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::catalog::Catalog;
+use crate::cli::Subcommand;
+use crate::registry::audit::audit_catalog;
+use crate::registry::load_catalog;
+use crate::registry::model::AppEntry;
+use crate::registry::remote::{is_plain_http, is_remote};
+use crate::system::exec::{
+    binaries_installed, detect_install_state, entry_command, is_cancelled, outdated_version,
+    InstallState,
+};
+use crate::system::os::Platform;
+use crate::utils::{format_elapsed, glob_match, is_glob};
+
+/// Exit codes of the non-interactive commands. Anything else that goes wrong exits with 1.
+pub const EXIT_COMMAND_FAILED: u8 = 2;
+pub const EXIT_CATALOG_ERROR: u8 = 3;
+pub const EXIT_UNKNOWN_ID: u8 = 4;
+pub const EXIT_CHECK_FAILED: u8 = 5;
+
+/// Runs a subcommand against the catalog without starting the TUI.
+/// `--check`: prints every problem as `<catalog>: <id>: <problem>` on stderr, for CI.
+/// A remote catalog is fetched first and its cached copy audited.
+pub fn check_catalog(source: &str) -> ExitCode {
+    let path = if is_remote(source) || is_plain_http(source) {
+        match load_catalog(source) {
+            Ok(catalog) => catalog.path,
+            Err(e) => {
+                eprintln!("{source}: {e:#}");
+                return ExitCode::from(EXIT_CATALOG_ERROR);
+            }
+        }
+    } else {
+        PathBuf::from(source)
+    };
+    if !path.exists() {
+        eprintln!("{source}: catalog not found");
+        return ExitCode::from(EXIT_CATALOG_ERROR);
+    }
+    let problems = audit_catalog(&path);
+    for problem in &problems {
+        eprintln!("{source}: {problem}");
+    }
+    if problems.is_empty() {
+        println!("{source}: ok");
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{source}: {} problem(s)", problems.len());
+        ExitCode::from(EXIT_CHECK_FAILED)
+    }
+}
+
+pub fn run_subcommand(command: Subcommand, catalog: Catalog) -> ExitCode {
+    let catalog = &catalog.on_retry(|note| eprintln!("{note}"));
+    let entries = catalog.entries();
+    let platform = catalog.platform();
+    match command {
+        Subcommand::List { json: false } => {
+            list(entries, platform);
+            ExitCode::SUCCESS
+        }
+        Subcommand::List { json: true } => match list_json(entries, platform) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("error: {e:#}");
+                ExitCode::FAILURE
+            }
+        },
+        Subcommand::Outdated => {
+            outdated(entries, platform);
+            ExitCode::SUCCESS
+        }
+        Subcommand::Install(ids) => match resolve_ids(&ids, catalog) {
+            Some(targets) => exit_code(install(&targets, catalog)),
+            None => ExitCode::from(EXIT_UNKNOWN_ID),
+        },
+        Subcommand::Uninstall(ids) => match resolve_ids(&ids, catalog) {
+            Some(targets) => exit_code(uninstall(&targets, catalog)),
+            None => ExitCode::from(EXIT_UNKNOWN_ID),
+        },
+    }
+}
+
+fn exit_code(failures: usize) -> ExitCode {
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_COMMAND_FAILED)
+    }
+}
+
+fn list(entries: &[AppEntry], platform: Platform) {
+    for entry in entries {
+        let state = detect_install_state(entry, platform);
+        println!(
+            "{:<16} {:<20} {:<14} {}",
+            entry.id,
+            entry.name,
+            entry.category,
+            state.label()
+        );
+    }
+}
+
+fn outdated(entries: &[AppEntry], platform: Platform) {
+    let mut found = 0;
+    for entry in entries {
+        if detect_install_state(entry, platform) != InstallState::Installed {
+            continue;
+        }
+        if let Some(installed) = outdated_version(entry, platform) {
+            found += 1;
+            println!(
+                "{:<16} {} -> {}",
+                entry.id,
+                installed,
+                entry.version.as_deref().unwrap_or_default()
+            );
+        }
+    }
+    if found == 0 {
+        println!("Everything with a known version is up to date.");
+    }
+}
+
+#[derive(Serialize)]
+struct ListedApp<'a> {
+    id: &'a str,
+    name: &'a str,
+    category: &'a str,
+    installed: bool,
+    state: &'static str,
+}
+
+fn list_json(entries: &[AppEntry], platform: Platform) -> Result<()> {
+    let listed: Vec<ListedApp> = entries
+        .iter()
+        .map(|entry| {
+            let state = detect_install_state(entry, platform);
+            ListedApp {
+                id: &entry.id,
+                name: &entry.name,
+                category: &entry.category,
+                installed: state == InstallState::Installed,
+                state: state.label(),
+            }
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&listed)?);
+    Ok(())
+}
+
+/// Looks up every requested id before anything runs, so a typo doesn't leave a half-done
+/// batch behind. Reports each unknown id and returns `None` if there were any.
+///
+/// Besides plain ids, `rip*` globs ids and aliases and `editor/*` globs `category/id`.
+/// Each pattern reports what it matched; one that matches nothing counts as unknown.
+/// Patterns skip `hidden` entries, which only an exact id reaches.
+fn resolve_ids<'a>(ids: &[String], catalog: &'a Catalog) -> Option<Vec<&'a AppEntry>> {
+    let mut targets: Vec<&AppEntry> = Vec::new();
+    let mut unknown = false;
+    for id in ids {
+        let pattern = id.contains('/') || is_glob(id);
+        let matched = if pattern {
+            let matched = match_pattern(id, catalog.entries());
+            if !matched.is_empty() {
+                let names = matched
+                    .iter()
+                    .map(|entry| entry.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("'{id}' matched {} apps: {names}", matched.len());
+            }
+            matched
+        } else {
+            catalog.get(id).into_iter().collect()
+        };
+        if matched.is_empty() && pattern {
+            eprintln!("error: '{id}' matched no apps in the catalog");
+            unknown = true;
+        } else if matched.is_empty() {
+            eprintln!("error: no app with id '{id}' in the catalog");
+            unknown = true;
+        }
+        for entry in matched {
+            if !targets.iter().any(|target| target.id == entry.id) {
+                targets.push(entry);
+            }
+        }
+    }
+    (!unknown).then_some(targets)
+}
+
+fn match_pattern<'a>(pattern: &str, entries: &'a [AppEntry]) -> Vec<&'a AppEntry> {
+    let (category, id) = match pattern.split_once('/') {
+        Some((category, id)) => (Some(category), id),
+        None => (None, pattern),
+    };
+    entries
+        .iter()
+        .filter(|entry| !entry.hidden)
+        .filter(|entry| category.is_none_or(|category| glob_match(category, &entry.category)))
+        .filter(|entry| {
+            glob_match(id, &entry.id) || entry.aliases.iter().any(|alias| glob_match(id, alias))
+        })
+        .collect()
+}
+
+fn install(targets: &[&AppEntry], catalog: &Catalog) -> usize {
+    let platform = catalog.platform();
+    let mut failures = 0;
+    for &entry in targets {
+        if detect_install_state(entry, platform) == InstallState::Installed {
+            println!("{} already installed", entry.name);
+            continue;
+        }
+        let Some(cmd) = entry_command(entry, &entry.install, platform) else {
+            eprintln!(
+                "error: no install command defined for {} on {}",
+                entry.name,
+                platform.label()
+            );
+            failures += 1;
+            continue;
+        };
+
+        println!("Installing {} using: {}", entry.name, cmd);
+        let started = Instant::now();
+        match catalog.install(&entry.id) {
+            Ok(()) => {
+                println!(
+                    "Installed {} successfully in {}.",
+                    entry.name,
+                    format_elapsed(started.elapsed())
+                );
+                if !binaries_installed(entry) {
+                    eprintln!(
+                        "warning: install command exited 0 but {} still not found",
+                        entry.binary
+                    );
+                }
+                post_install(entry, catalog);
+            }
+            Err(e) => {
+                eprintln!("error: install failed for {}: {e:#}", entry.name);
+                failures += 1;
+                if is_cancelled(&e) {
+                    break;
+                }
+            }
+        }
+    }
+    failures
+}
+
+/// Runs the entry's post-install step, if any. Failure only warns: the app is installed.
+fn post_install(entry: &AppEntry, catalog: &Catalog) {
+    let Some(cmd) = entry
+        .post_install
+        .as_ref()
+        .and_then(|commands| entry_command(entry, commands, catalog.platform()))
+    else {
+        return;
+    };
+    println!("Running post-install step for {}: {}", entry.name, cmd);
+    if let Err(e) = catalog.post_install(&entry.id) {
+        eprintln!(
+            "warning: post-install step failed for {}: {e:#}",
+            entry.name
+        );
+    }
+}
+
+fn uninstall(targets: &[&AppEntry], catalog: &Catalog) -> usize {
+    let platform = catalog.platform();
+    let mut failures = 0;
+    for &entry in targets {
+        if detect_install_state(entry, platform) != InstallState::Installed {
+            println!("{} not installed", entry.name);
+            continue;
+        }
+        let Some(cmd) = entry_command(entry, &entry.uninstall, platform) else {
+            eprintln!(
+                "error: no uninstall command defined for {} on {}",
+                entry.name,
+                platform.label()
+            );
+            failures += 1;
+            continue;
+        };
+
+        println!("Uninstalling {} using: {}", entry.name, cmd);
+        let started = Instant::now();
+        match catalog.uninstall(&entry.id) {
+            Ok(()) => println!(
+                "Uninstalled {} successfully in {}.",
+                entry.name,
+                format_elapsed(started.elapsed())
+            ),
+            Err(e) => {
+                eprintln!("error: uninstall failed for {}: {e:#}", entry.name);
+                failures += 1;
+                if is_cancelled(&e) {
+                    break;
+                }
+            }
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::registry::model::test_entry_json;
+
+    fn resolved(pattern: &str, catalog: &Catalog) -> Vec<String> {
+        resolve_ids(&[pattern.to_string()], catalog)
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| entry.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn patterns_skip_hidden_entries_but_exact_ids_reach_them() {
+        let path = std::env::temp_dir().join(format!("tuihub-test-{}.json", std::process::id()));
+        let entries = [
+            test_entry_json("ripgrep", json!({"category": "Search"})),
+            test_entry_json("ripgrep-old", json!({"category": "Search", "hidden": true})),
+        ];
+        fs::write(&path, serde_json::to_string(&entries).unwrap()).unwrap();
+        let catalog = Catalog::load(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        let catalog = catalog.unwrap();
+
+        assert_eq!(resolved("rip*", &catalog), ["ripgrep"]);
+        assert_eq!(resolved("Search/*", &catalog), ["ripgrep"]);
+        assert_eq!(resolved("ripgrep-old", &catalog), ["ripgrep-old"]);
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::app::actions::PauseAfter;
+use crate::app::state::{LaunchLimits, LogRetention, PollInterval};
+use crate::system::paths::config_dir;
+use crate::system::tmux::TmuxTarget;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub theme: Option<String>,
+    pub log_retention_secs: Option<u64>,
+    pub error_log_retention_secs: Option<u64>,
+    /// Whether to wait for a key before leaving install/uninstall output.
+    pub pause_after_command: PauseAfter,
+    pub confirm_quit: bool,
+    /// Click rows and tabs, scroll with the wheel. Off by default since it gets in the way
+    /// of selecting text in the terminal.
+    pub mouse: bool,
+    pub launch_confirm_above: Option<usize>,
+    pub max_launch: Option<usize>,
+    pub poll_ms: Option<u64>,
+    pub tmux_target: Option<TmuxTarget>,
+    /// Environment added to every install/uninstall command; catalog entries override it.
+    pub env: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// `TUIHUB_LOG_RETENTION` wins over the config file; errors default to lingering longer
+    /// than the base retention so there is time to read them.
+    pub fn log_retention(&self) -> LogRetention {
+        let defaults = LogRetention::default();
+        let info = std::env::var("TUIHUB_LOG_RETENTION")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .or(self.log_retention_secs)
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.info);
+        let error = self
+            .error_log_retention_secs
+            .map(Duration::from_secs)
+            .unwrap_or_else(|| info.max(defaults.error));
+        LogRetention { info, error }
+    }
+
+    pub fn launch_limits(&self) -> LaunchLimits {
+        let defaults = LaunchLimits::default();
+        LaunchLimits {
+            confirm_above: self.launch_confirm_above.unwrap_or(defaults.confirm_above),
+            max: self.max_launch.unwrap_or(defaults.max),
+        }
+    }
+
+    /// `TUIHUB_POLL_MS` wins over the config file. It sets the busy interval; idle waits ten
+    /// times as long.
+    pub fn poll_interval(&self) -> PollInterval {
+        let Some(ms) = std::env::var("TUIHUB_POLL_MS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .or(self.poll_ms)
+            .filter(|ms| *ms > 0)
+        else {
+            return PollInterval::default();
+        };
+        PollInterval {
+            active: Duration::from_millis(ms),
+            idle: Duration::from_millis(ms.saturating_mul(10)),
+        }
+    }
+
+    /// `TUIHUB_TMUX_TARGET` wins over the config file; unknown values fall through.
+    pub fn tmux_target(&self) -> TmuxTarget {
+        std::env::var("TUIHUB_TMUX_TARGET")
+            .ok()
+            .and_then(|value| TmuxTarget::parse(&value))
+            .or(self.tmux_target)
+            .unwrap_or_default()
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// Reads `config.toml`, returning defaults plus a warning if the file is unreadable.
+    pub fn load() -> (Self, Option<String>) {
+        let Some(raw) = Self::path().and_then(|path| fs::read_to_string(path).ok()) else {
+            return (Self::default(), None);
+        };
+        match toml::from_str(&raw) {
+            Ok(config) => (config, None),
+            Err(e) => (
+                Self::default(),
+                Some(format!("config.toml ignored: {}", e.message())),
+            ),
+        }
+    }
+
+    /// Updates a single top-level key in `config.toml`, keeping the other keys intact.
+    pub fn store(key: &str, value: impl Into<toml::Value>) -> Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        let mut table: toml::Table = fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| raw.parse().ok())
+            .unwrap_or_default();
+        table.insert(key.to_string(), value.into());
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(&path, toml::to_string(&table)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
@@ -0,0 +1,19 @@
+//! TUIHub's engine: catalog loading, install detection and install/uninstall execution.
+//! [`Catalog`] is the entry point for tools that want those without the TUI; the `tuihub`
+//! binary is a thin wrapper over this crate.
+
+pub mod app;
+pub mod catalog;
+pub mod cli;
+pub mod commands;
+pub mod config;
+pub mod input;
+pub mod registry;
+pub mod system;
+pub mod ui;
+pub mod utils;
+
+pub use catalog::Catalog;
+pub use registry::model::AppEntry;
+pub use system::exec::InstallState;
+pub use system::os::Platform;
@@ -1,41 +1,198 @@
 use std::io;
+use std::process::ExitCode;
 
 use anyhow::{Context, Result};
 use crossterm::{
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 
-mod app;
-mod input;
-mod registry;
-mod system;
-mod ui;
-mod utils;
+use tuihub::app::actions::set_mouse_capture;
+use tuihub::app::state::LogLevel;
+use tuihub::app::{refresh_filter, run, App};
+use tuihub::cli::{CliOptions, USAGE};
+use tuihub::commands::{check_catalog, run_subcommand, EXIT_CATALOG_ERROR};
+use tuihub::config::Config;
+use tuihub::registry::remote::is_remote;
+use tuihub::registry::{default_catalog_path, load_catalog_or_empty};
+use tuihub::system::exec::install_interrupt_handler;
+use tuihub::system::os::Platform;
+use tuihub::system::watch::CatalogWatcher;
+use tuihub::ui::glyphs;
+use tuihub::ui::theme::{Theme, ThemePreset};
+use tuihub::{AppEntry, Catalog};
 
-use app::{refresh_filter, run, App};
-use registry::load_entries;
+fn main() -> Result<ExitCode> {
+    let options = CliOptions::parse()?;
+    if options.help {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
+    }
+    if options.print_schema {
+        let schema = schemars::schema_for!(Vec<AppEntry>);
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let catalog_source = options
+        .catalog
+        .clone()
+        .unwrap_or_else(|| default_catalog_path().to_string());
+    if options.check {
+        return Ok(check_catalog(&catalog_source));
+    }
+    let (config, config_warning) = Config::load();
 
-fn main() -> Result<()> {
-    let entries = load_entries("data/apps.json")?;
-    let mut app = App::new(entries);
+    if let Some(command) = options.command {
+        let catalog = match Catalog::load(&catalog_source) {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                eprintln!("error: {e:#}");
+                return Ok(ExitCode::from(EXIT_CATALOG_ERROR));
+            }
+        };
+        if let Some(warning) = catalog.warning() {
+            eprintln!("warning: {warning}");
+        }
+        if let Some(warning) = config_warning {
+            eprintln!("warning: {warning}");
+        }
+        let platform = options.platform.unwrap_or_else(Platform::detect);
+        let catalog = catalog.with_platform(platform).with_env(config.env);
+        return Ok(run_subcommand(command, catalog));
+    }
+    let catalog = load_catalog_or_empty(&catalog_source)?;
+    let mut app = App::new(catalog.entries, catalog.path);
+    if !app.new_ids.is_empty() {
+        app.log(
+            format!(
+                "{} new apps since the last run. Press N to mark them seen.",
+                app.new_ids.len()
+            ),
+            LogLevel::Info,
+        );
+    }
+    if is_remote(&catalog_source) {
+        app.catalog_url = Some(catalog_source);
+    }
+    if let Some(warning) = catalog.warning {
+        app.log(warning, LogLevel::Error);
+    }
+    if let Some(platform) = options.platform {
+        app.override_platform(platform);
+    }
+    let installable = app.installable_count();
+    app.log(
+        format!(
+            "{installable}/{} installable on {}",
+            app.entries.len(),
+            app.platform.label()
+        ),
+        if installable == 0 && !app.entries.is_empty() {
+            LogLevel::Error
+        } else {
+            LogLevel::Info
+        },
+    );
     refresh_filter(&mut app);
 
+    app.log_retention = config.log_retention();
+    app.launch_limits = config.launch_limits();
+    app.poll_interval = config.poll_interval();
+    app.tmux_target = config.tmux_target();
+    app.pause_after_command = config.pause_after_command;
+    app.confirm_quit = config.confirm_quit;
+    app.command_env = config.env.clone();
+    if let Some(warning) = config_warning {
+        app.log(warning, LogLevel::Error);
+    }
+
+    let preset = match options.theme {
+        Some(preset) => {
+            let _ = Config::store("theme", preset.name());
+            preset
+        }
+        None => config
+            .theme
+            .as_deref()
+            .and_then(ThemePreset::from_name)
+            .unwrap_or_default(),
+    };
+    app.theme_preset = preset;
+    let ascii = options.ascii || glyphs::ascii_terminal();
+    glyphs::set_ascii(ascii);
+    app.show_icons = !options.no_icons && !ascii;
+    if options.no_color || Theme::no_color_requested() {
+        app.theme = Theme::monochrome();
+    } else {
+        let (theme, theme_warnings) = Theme::load(preset);
+        app.theme = theme;
+        for warning in theme_warnings {
+            app.log(warning, LogLevel::Error);
+        }
+    }
+
+    if options.watch && app.catalog_url.is_some() {
+        app.set_status("Catalog watch only works for local files; press R to refetch.");
+    } else if options.watch {
+        match CatalogWatcher::spawn(&app.catalog_path) {
+            Ok(watcher) => app.catalog_watcher = Some(watcher),
+            Err(e) => {
+                app.set_status_level(format!("Catalog watch disabled: {e:#}"), LogLevel::Error)
+            }
+        }
+    }
+
+    install_interrupt_handler()?;
+    install_panic_hook();
     enable_raw_mode().context("failed to enable raw mode")?;
-    execute!(io::stdout(), EnterAlternateScreen).context("failed to enter alt screen")?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableBracketedPaste)
+        .context("failed to enter alt screen")?;
+    if options.mouse || config.mouse {
+        set_mouse_capture(true).context("failed to enable mouse capture")?;
+    }
 
     struct TerminalGuard;
     impl Drop for TerminalGuard {
         fn drop(&mut self) {
             let _ = disable_raw_mode();
-            let _ = execute!(io::stdout(), LeaveAlternateScreen);
+            let _ = execute!(
+                io::stdout(),
+                DisableMouseCapture,
+                DisableBracketedPaste,
+                LeaveAlternateScreen
+            );
         }
     }
-    let _guard = TerminalGuard;
+    let guard = TerminalGuard;
 
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend).context("failed to init terminal")?;
 
-    run(&mut app, &mut terminal)
+    let result = run(&mut app, &mut terminal);
+    let save_result = app.save_state();
+    drop(guard);
+
+    if let Err(e) = save_result {
+        eprintln!("warning: failed to save session state: {e:#}");
+    }
+    result.map(|()| ExitCode::SUCCESS)
+}
+
+/// Restores the terminal before the default hook prints the panic, so the message lands on
+/// the normal screen instead of a raw-mode alt screen that is about to vanish.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(
+            io::stdout(),
+            DisableMouseCapture,
+            DisableBracketedPaste,
+            LeaveAlternateScreen
+        );
+        default_hook(info);
+    }));
 }
@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::loader::{is_json5, name_conflicts};
+use super::model::{AppEntry, ArchCommand, LinuxCommand};
+use crate::system::exec::ARCH_KEYS;
+use crate::system::os::PkgMgr;
+
+const PLATFORM_KEYS: [&str; 4] = ["linux", "wsl", "mac", "windows"];
+const COMMAND_FIELDS: [&str; 3] = ["install", "uninstall", "post_install"];
+
+/// Every problem in the catalog at `path`, one `<id>: <problem>` line each, instead of
+/// stopping at the first like loading does. An empty list means the catalog is clean.
+pub fn audit_catalog(path: &Path) -> Vec<String> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) => return vec![format!("cannot read catalog: {e}")],
+    };
+    let parsed: Result<Value, String> = if is_json5(path) {
+        json5::from_str(&raw).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(&raw).map_err(|e| e.to_string())
+    };
+    let items = match parsed {
+        Ok(Value::Array(items)) => items,
+        Ok(_) => return vec!["catalog must be a JSON array of entries".to_string()],
+        Err(e) => return vec![format!("invalid catalog: {e}")],
+    };
+
+    let mut problems = Vec::new();
+    let mut entries = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let label = item
+            .get("id")
+            .and_then(Value::as_str)
+            .filter(|id| !id.trim().is_empty())
+            .map_or_else(|| format!("#{index}"), str::to_string);
+        problems.extend(
+            unknown_keys(&item)
+                .into_iter()
+                .map(|problem| format!("{label}: {problem}")),
+        );
+        match serde_json::from_value::<AppEntry>(item) {
+            Ok(entry) => {
+                problems.extend(
+                    entry_problems(&entry)
+                        .into_iter()
+                        .map(|problem| format!("{label}: {problem}")),
+                );
+                entries.push(entry);
+            }
+            Err(e) => problems.push(format!("{label}: {e}")),
+        }
+    }
+    problems.extend(name_conflicts(&entries));
+    problems
+}
+
+/// Platform, package manager and architecture keys that serde would silently ignore, e.g. a
+/// `macos` typo.
+fn unknown_keys(item: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    for field in COMMAND_FIELDS {
+        let Some(commands) = item.get(field).and_then(Value::as_object) else {
+            continue;
+        };
+        for (platform, command) in commands {
+            if !PLATFORM_KEYS.contains(&platform.as_str()) {
+                problems.push(format!("{field}: unknown platform '{platform}'"));
+                continue;
+            }
+            let Some(variants) = command.as_object() else {
+                continue;
+            };
+            for key in variants.keys() {
+                let known = key == "default"
+                    || ARCH_KEYS.contains(&key.as_str())
+                    || (platform == "linux" && PkgMgr::from_name(key).is_some());
+                if known {
+                    continue;
+                }
+                problems.push(if platform == "linux" {
+                    format!("{field}.linux: unknown package manager or architecture '{key}'")
+                } else {
+                    format!("{field}.{platform}: unknown architecture '{key}'")
+                });
+            }
+        }
+    }
+    problems
+}
+
+fn entry_problems(entry: &AppEntry) -> Vec<String> {
+    let mut problems = Vec::new();
+    let required = [
+        ("id", entry.id.as_str()),
+        ("name", entry.name.as_str()),
+        ("description", entry.description.as_str()),
+        ("category", entry.category.as_str()),
+        ("repo", entry.repo.as_str()),
+    ];
+    for (field, value) in required {
+        if value.trim().is_empty() {
+            problems.push(format!("empty {field}"));
+        }
+    }
+    if entry.binary.all().is_empty() {
+        problems.push("empty binary list".to_string());
+    } else if entry
+        .binary
+        .all()
+        .iter()
+        .any(|binary| binary.trim().is_empty())
+    {
+        problems.push("empty binary".to_string());
+    }
+    let commands = &entry.install;
+    let linux_empty = match &commands.linux {
+        LinuxCommand::Single(cmd) => cmd.trim().is_empty(),
+        LinuxCommand::ByManager(variants) => variants.values().all(|cmd| cmd.trim().is_empty()),
+    };
+    let arch_empty = |command: &ArchCommand| match command {
+        ArchCommand::Single(cmd) => cmd.trim().is_empty(),
+        ArchCommand::ByArch(variants) => variants.values().all(|cmd| cmd.trim().is_empty()),
+    };
+    if linux_empty
+        && [&commands.wsl, &commands.mac, &commands.windows]
+            .into_iter()
+            .all(arch_empty)
+    {
+        problems.push("no install command on any platform".to_string());
+    }
+    problems
+}
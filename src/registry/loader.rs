@@ -1,14 +1,167 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
 use super::model::AppEntry;
+use super::remote::{fetch_catalog, is_plain_http, is_remote};
+
+const CATALOG_PATH: &str = "data/apps.json";
+const CATALOG_JSON5_PATH: &str = "data/apps.json5";
+
+/// Prefers the JSON5 catalog when one exists, so comments and trailing commas are opt-in.
+pub fn default_catalog_path() -> &'static str {
+    if Path::new(CATALOG_JSON5_PATH).exists() {
+        CATALOG_JSON5_PATH
+    } else {
+        CATALOG_PATH
+    }
+}
+
+/// A catalog ready to use, plus where it was read from on disk.
+pub struct LoadedCatalog {
+    pub entries: Vec<AppEntry>,
+    /// The local file, or the cached copy of a remote catalog.
+    pub path: PathBuf,
+    /// Set when a remote catalog could not be fetched and the cache was used instead.
+    pub warning: Option<String>,
+}
+
+/// Loads a catalog from a local path or an https URL.
+pub fn load_catalog(source: &str) -> Result<LoadedCatalog> {
+    if is_plain_http(source) {
+        anyhow::bail!("refusing to fetch a catalog over plain http; use an https:// URL");
+    }
+    if is_remote(source) {
+        return fetch_catalog(source);
+    }
+    Ok(LoadedCatalog {
+        entries: load_entries(source)?,
+        path: PathBuf::from(source),
+        warning: None,
+    })
+}
+
+/// Like [`load_catalog`], but a local catalog that doesn't exist yet loads as an empty list,
+/// so the TUI can start on its empty-state screen instead of failing.
+pub fn load_catalog_or_empty(source: &str) -> Result<LoadedCatalog> {
+    let local = !is_remote(source) && !is_plain_http(source);
+    match fs::metadata(source) {
+        Err(e) if local && e.kind() == io::ErrorKind::NotFound => Ok(LoadedCatalog {
+            entries: Vec::new(),
+            path: PathBuf::from(source),
+            warning: None,
+        }),
+        _ => load_catalog(source),
+    }
+}
 
 pub fn load_entries(path: impl AsRef<Path>) -> Result<Vec<AppEntry>> {
     let file = fs::read_to_string(path.as_ref())
         .with_context(|| format!("failed to read {}", path.as_ref().display()))?;
-    let entries: Vec<AppEntry> = serde_json::from_str(&file)
-        .with_context(|| format!("invalid json in {}", path.as_ref().display()))?;
+    parse_entries(
+        &file,
+        is_json5(path.as_ref()),
+        &path.as_ref().display().to_string(),
+    )
+}
+
+/// Parses catalog text; `source` only names it in error messages.
+pub fn parse_entries(raw: &str, json5: bool, source: &str) -> Result<Vec<AppEntry>> {
+    if raw.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let entries: Vec<AppEntry> = if json5 {
+        json5::from_str(raw).with_context(|| format!("invalid json5 in {source}"))?
+    } else {
+        serde_json::from_str(raw).with_context(|| format!("invalid json in {source}"))?
+    };
+    validate_names(&entries).with_context(|| format!("invalid catalog {source}"))?;
     Ok(entries)
 }
+
+pub fn is_json5(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json5"))
+}
+
+fn validate_names(entries: &[AppEntry]) -> Result<()> {
+    match name_conflicts(entries).into_iter().next() {
+        Some(conflict) => anyhow::bail!(conflict),
+        None => Ok(()),
+    }
+}
+
+/// Ids and aliases share one case-insensitive namespace, since either can be typed on the
+/// command line to pick an entry.
+pub fn name_conflicts(entries: &[AppEntry]) -> Vec<String> {
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    let mut conflicts = Vec::new();
+    for entry in entries {
+        for name in std::iter::once(&entry.id).chain(&entry.aliases) {
+            if let Some(owner) = seen.insert(name.to_ascii_lowercase(), &entry.id) {
+                conflicts.push(format!(
+                    "'{name}' is used by both '{owner}' and '{}'",
+                    entry.id
+                ));
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMENTED: &str = r#"[
+        // Comments and trailing commas are what .json5 catalogs are for.
+        {
+            id: "fd",
+            name: "fd",
+            description: "find alternative",
+            category: "Files",
+            repo: "https://github.com/sharkdp/fd",
+            binary: "fd",
+            /* one command for every platform */
+            install: {
+                linux: "apt install fd-find",
+                wsl: "apt install fd-find",
+                mac: "brew install fd",
+                windows: "winget install fd",
+            },
+            uninstall: {
+                linux: "apt remove fd-find",
+                wsl: "apt remove fd-find",
+                mac: "brew uninstall fd",
+                windows: "winget uninstall fd",
+            },
+        },
+    ]"#;
+
+    #[test]
+    fn parses_json5_with_comments_and_trailing_commas() {
+        let entries = parse_entries(COMMENTED, true, "apps.json5").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "fd");
+        assert_eq!(entries[0].binary.primary(), "fd");
+    }
+
+    #[test]
+    fn plain_json_stays_strict() {
+        let err = parse_entries(COMMENTED, false, "apps.json").unwrap_err();
+        assert!(format!("{err:#}").contains("invalid json in apps.json"));
+    }
+
+    #[test]
+    fn missing_catalog_is_an_error_except_at_tui_startup() {
+        let path =
+            std::env::temp_dir().join(format!("tuihub-test-missing-{}.json", std::process::id()));
+        let source = path.to_str().unwrap();
+        assert!(load_catalog(source).is_err());
+        assert!(load_catalog_or_empty(source).unwrap().entries.is_empty());
+        assert!(load_catalog_or_empty("http://example.com/apps.json").is_err());
+    }
+}
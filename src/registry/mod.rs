@@ -1,4 +1,6 @@
+pub mod audit;
 pub mod loader;
 pub mod model;
+pub mod remote;
 
-pub use loader::load_entries;
+pub use loader::{default_catalog_path, load_catalog, load_catalog_or_empty};
@@ -1,21 +1,176 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use schemars::JsonSchema;
 use serde::Deserialize;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, JsonSchema)]
 pub struct AppEntry {
+    /// Unique, stable identifier used for selection and tmux names.
     pub id: String,
+    /// Alternate names (e.g. `rg` for ripgrep) accepted by search and the CLI.
+    #[serde(default)]
+    pub aliases: Vec<String>,
     pub name: String,
     pub description: String,
     pub category: String,
+    /// Emoji or nerd-font glyph shown before the name. Defaults to one picked from the
+    /// category.
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Source repository URL; opened with `O` when there is no `homepage`.
     pub repo: String,
-    pub binary: String,
+    /// Project website, preferred over `repo` by `O`.
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// Executable looked up on PATH to decide whether the app is installed, or a list of them
+    /// for suites that install several. The first one is what gets launched.
+    pub binary: Binaries,
+    /// With several binaries, whether `all` (the default) or `any` must be on PATH.
+    #[serde(default)]
+    pub binary_match: BinaryMatch,
+    /// Shell command tmux runs to start the app, e.g. `nvim +Telescope`. Defaults to `binary`.
+    #[serde(default)]
+    pub launch_command: Option<String>,
+    /// Optional shell command that must succeed to confirm `binary` is this app.
+    #[serde(default)]
+    pub verify: Option<String>,
+    /// Latest known release, compared against what `version_command` reports.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Shell command that prints the installed version, e.g. `btop --version`.
+    #[serde(default)]
+    pub version_command: Option<String>,
+    /// Extra install attempts after a transient failure.
+    #[serde(default)]
+    pub retries: Option<u32>,
+    /// Whether the commands may prompt (sudo, confirmations). Non-interactive ones run with
+    /// their output captured while the TUI stays up. Defaults to `true`.
+    #[serde(default)]
+    pub interactive: Option<bool>,
+    /// Extra environment for install/uninstall commands; values may use `${VAR}`.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
     pub install: InstallCommands,
     pub uninstall: InstallCommands,
+    /// Setup step run after a successful install; its failure only warns.
+    #[serde(default)]
+    pub post_install: Option<InstallCommands>,
+    /// Kept in the catalog but left out of the list (deprecated, work in progress). Still
+    /// reachable by exact id from the command line.
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl AppEntry {
+    /// What tmux runs to start this app.
+    pub fn launch_command(&self) -> &str {
+        self.launch_command
+            .as_deref()
+            .unwrap_or(self.binary.primary())
+    }
+
+    /// Unset means interactive, so a missing flag never swallows a prompt.
+    pub fn is_interactive(&self) -> bool {
+        self.interactive.unwrap_or(true)
+    }
+
+    /// The link to open for this app: its homepage if set, otherwise the repo.
+    pub fn link(&self) -> &str {
+        self.homepage.as_deref().unwrap_or(&self.repo)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Binaries {
+    Single(String),
+    Many(Vec<String>),
+}
+
+impl Binaries {
+    /// The binary that stands for the app when launching or locating it.
+    pub fn primary(&self) -> &str {
+        self.all().first().map_or("", String::as_str)
+    }
+
+    pub fn all(&self) -> &[String] {
+        match self {
+            Binaries::Single(binary) => std::slice::from_ref(binary),
+            Binaries::Many(binaries) => binaries,
+        }
+    }
+}
+
+impl fmt::Display for Binaries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.all().join(", "))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BinaryMatch {
+    #[default]
+    All,
+    Any,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// One shell command per platform. Leave a platform empty when it is unsupported.
+#[derive(Debug, Clone, PartialEq, Deserialize, JsonSchema)]
 pub struct InstallCommands {
-    pub linux: String,
-    pub wsl: String,
-    pub mac: String,
-    pub windows: String,
+    pub linux: LinuxCommand,
+    pub wsl: ArchCommand,
+    pub mac: ArchCommand,
+    pub windows: ArchCommand,
+}
+
+/// Either one command for every distro, or commands keyed by package manager
+/// (`apt`, `dnf`, `pacman`, ...) with an optional `default` fallback. Architecture keys
+/// (`x86_64`, `aarch64`) may sit in the same map; they are tried after the package managers.
+#[derive(Debug, Clone, PartialEq, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum LinuxCommand {
+    Single(String),
+    ByManager(BTreeMap<String, String>),
+}
+
+/// Either one command, or commands keyed by CPU architecture (`x86_64`, `aarch64`, or the
+/// `amd64`/`arm64` spellings release assets use) with an optional `default` fallback.
+#[derive(Debug, Clone, PartialEq, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ArchCommand {
+    Single(String),
+    ByArch(BTreeMap<String, String>),
+}
+
+/// A catalog entry for tests, as JSON: `id` doubles as its name, every command is `true`, and
+/// the binary is `tuihub-test-{id}` so it is never on PATH. `fields` override any of that.
+#[cfg(test)]
+pub(crate) fn test_entry_json(id: &str, fields: serde_json::Value) -> serde_json::Value {
+    let commands = serde_json::json!({
+        "linux": "true", "wsl": "true", "mac": "true", "windows": "true"
+    });
+    let mut entry = serde_json::json!({
+        "id": id,
+        "name": id,
+        "description": "test entry",
+        "category": "Test",
+        "repo": "https://example.com",
+        "binary": format!("tuihub-test-{id}"),
+        "install": commands,
+        "uninstall": commands
+    });
+    if let serde_json::Value::Object(fields) = fields {
+        entry.as_object_mut().unwrap().extend(fields);
+    }
+    entry
+}
+
+/// [`test_entry_json`], parsed.
+#[cfg(test)]
+pub(crate) fn test_entry(id: &str, fields: serde_json::Value) -> AppEntry {
+    serde_json::from_value(test_entry_json(id, fields)).unwrap()
 }
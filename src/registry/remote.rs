@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use super::loader::{is_json5, load_entries, parse_entries, LoadedCatalog};
+use crate::system::paths::cache_dir;
+use crate::utils::stable_hash;
+
+/// Long enough for a slow link, short enough that startup never appears to hang.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn is_remote(source: &str) -> bool {
+    source.starts_with("https://")
+}
+
+/// An `http://` catalog is refused rather than fetched: anyone on the network path could
+/// rewrite the install commands it carries.
+pub fn is_plain_http(source: &str) -> bool {
+    source.starts_with("http://")
+}
+
+/// Downloads a catalog and refreshes its cached copy. If the download fails, falls back to
+/// the last copy that parsed, with a warning.
+pub fn fetch_catalog(url: &str) -> Result<LoadedCatalog> {
+    let path = cache_path(url)?;
+    let fetched =
+        download(url).and_then(|raw| Ok((parse_entries(&raw, is_json5(&path), url)?, raw)));
+    match fetched {
+        Ok((entries, raw)) => Ok(LoadedCatalog {
+            warning: write_cache(&path, &raw)
+                .err()
+                .map(|e| format!("Catalog not cached: {e:#}")),
+            entries,
+            path,
+        }),
+        Err(e) if path.exists() => Ok(LoadedCatalog {
+            entries: load_entries(&path)?,
+            warning: Some(format!("Using cached catalog, fetch failed: {e:#}")),
+            path,
+        }),
+        Err(e) => Err(e.context("no cached copy to fall back to")),
+    }
+}
+
+fn download(url: &str) -> Result<String> {
+    let agent = ureq::AgentBuilder::new().timeout(FETCH_TIMEOUT).build();
+    // ureq's errors already name the URL.
+    let response = agent.get(url).call()?;
+    response
+        .into_string()
+        .with_context(|| format!("failed to read {url}"))
+}
+
+/// One cache file per URL, keeping the `.json5` extension so it is parsed the same way.
+fn cache_path(url: &str) -> Result<PathBuf> {
+    let dir = cache_dir().context("no cache directory for remote catalogs")?;
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let ext = if is_json5(Path::new(path)) {
+        "json5"
+    } else {
+        "json"
+    };
+    Ok(dir.join(format!("catalog-{:016x}.{ext}", stable_hash(url))))
+}
+
+fn write_cache(path: &Path, raw: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    fs::write(path, raw).with_context(|| format!("failed to write {}", path.display()))
+}
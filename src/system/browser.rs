@@ -0,0 +1,110 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use super::os::Platform;
+
+pub fn looks_like_url(input: &str) -> bool {
+    let trimmed = input.trim();
+    let rest = match trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))
+    {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let host = rest.split('/').next().unwrap_or_default();
+    !host.is_empty() && host.contains('.') && !trimmed.chars().any(char::is_whitespace)
+}
+
+pub fn open_url(url: &str, platform: Platform) -> Result<()> {
+    if !looks_like_url(url) {
+        anyhow::bail!("not a valid http(s) URL: {url}");
+    }
+
+    if platform == Platform::Wsl {
+        return open_url_wsl(url);
+    }
+
+    if open::that_detached(url).is_ok() {
+        return Ok(());
+    }
+
+    let (program, args): (&str, Vec<&str>) = match platform {
+        Platform::Mac => ("open", vec![url]),
+        Platform::Windows => ("rundll32", vec![WINDOWS_URL_HANDLER, url]),
+        _ => ("xdg-open", vec![url]),
+    };
+    spawn_opener(program, &args)
+}
+
+/// Opens a URL on Windows without going through `cmd /C start`, which would run anything
+/// after a `&` or `|` in the URL as a second command.
+const WINDOWS_URL_HANDLER: &str = "url.dll,FileProtocolHandler";
+
+/// Opens a local directory (or file) in the system file manager.
+pub fn open_path(path: &Path, platform: Platform) -> Result<()> {
+    if platform == Platform::Wsl {
+        return open_path_wsl(path);
+    }
+
+    if open::that_detached(path).is_ok() {
+        return Ok(());
+    }
+
+    let path = path.to_string_lossy();
+    let (program, args): (&str, Vec<&str>) = match platform {
+        Platform::Mac => ("open", vec![&path]),
+        Platform::Windows => ("explorer", vec![&path]),
+        _ => ("xdg-open", vec![&path]),
+    };
+    spawn_opener(program, &args)
+}
+
+fn open_path_wsl(path: &Path) -> Result<()> {
+    let path = path.to_string_lossy();
+    if which::which("wslview").is_ok() {
+        return spawn_opener("wslview", &[&path]);
+    }
+    let output = Command::new("wslpath")
+        .args(["-w", &path])
+        .output()
+        .context("failed to run wslpath")?;
+    let windows_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || windows_path.is_empty() {
+        anyhow::bail!("wslpath could not translate {path}");
+    }
+    // explorer.exe exits 1 even when the window opens, so its status means nothing.
+    Command::new("explorer.exe")
+        .arg(&windows_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .context("failed to run explorer.exe")?;
+    Ok(())
+}
+
+fn open_url_wsl(url: &str) -> Result<()> {
+    if which::which("wslview").is_ok() {
+        return spawn_opener("wslview", &[url]);
+    }
+    spawn_opener("rundll32.exe", &[WINDOWS_URL_HANDLER, url])
+}
+
+fn spawn_opener(program: &str, args: &[&str]) -> Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .with_context(|| format!("failed to run {program}"))?;
+
+    if !status.success() {
+        anyhow::bail!("{program} failed with status {status}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+
+use regex::Regex;
+use which::which;
+
+use super::exec::shell_quote;
+use super::os::Platform;
+
+/// How many trailing lines of a command's output are kept for error reports.
+pub const TAIL_LINES: usize = 20;
+
+/// The last few lines written by a child process, shared between its stdout and stderr
+/// readers so they stay in roughly the order they were printed.
+#[derive(Clone, Default)]
+pub struct OutputTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl OutputTail {
+    /// Copies `source` to `sink` as it arrives while recording complete lines.
+    pub fn tee(
+        &self,
+        mut source: impl Read + Send + 'static,
+        mut sink: impl Write + Send + 'static,
+    ) -> JoinHandle<()> {
+        let tail = self.clone();
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            let mut current = Vec::new();
+            while let Ok(n) = source.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                let _ = sink.write_all(&buf[..n]);
+                let _ = sink.flush();
+                tail.feed(&buf[..n], &mut current);
+            }
+            if !current.is_empty() {
+                tail.push(&current);
+            }
+        })
+    }
+
+    /// Records output captured elsewhere, such as a [`Transcript`]. Terminal escapes are
+    /// dropped and the pty's `\r\n` line ends read as plain newlines.
+    pub fn record(&self, output: &str) {
+        static ESCAPES: OnceLock<Regex> = OnceLock::new();
+        let escapes = ESCAPES.get_or_init(|| {
+            Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]")
+                .expect("escape pattern is valid")
+        });
+        let output = output.replace("\r\n", "\n");
+        let mut current = Vec::new();
+        self.feed(escapes.replace_all(&output, "").as_bytes(), &mut current);
+        if !current.is_empty() {
+            self.push(&current);
+        }
+    }
+
+    fn feed(&self, bytes: &[u8], current: &mut Vec<u8>) {
+        for &byte in bytes {
+            match byte {
+                b'\n' => self.push(&current.split_off(0)),
+                // Progress bars redraw with \r; keep only the final state.
+                b'\r' => current.clear(),
+                _ => current.push(byte),
+            }
+        }
+    }
+
+    fn push(&self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line).trim_end().to_string();
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines.push_back(line);
+        if lines.len() > TAIL_LINES {
+            lines.pop_front();
+        }
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines.iter().cloned().collect()
+    }
+}
+
+/// Records an interactive command through `script(1)`. The command keeps the real terminal,
+/// so prompts, colors and progress bars behave, and its output tail is still available for
+/// failure reports.
+pub struct Transcript {
+    /// A private directory made just for this transcript, removed on drop.
+    dir: PathBuf,
+    path: PathBuf,
+    bsd: bool,
+}
+
+impl Transcript {
+    /// `None` when there is no `script` to record with; the command then runs on the
+    /// terminal without a tail.
+    pub fn new(platform: Platform) -> Option<Self> {
+        if platform == Platform::Windows || which("script").is_err() {
+            return None;
+        }
+        let dir = private_temp_dir().ok()?;
+        Some(Self {
+            path: dir.join("typescript"),
+            dir,
+            bsd: platform == Platform::Mac,
+        })
+    }
+
+    /// `shell arg cmd`, run under `script` with its exit status passed through.
+    pub fn command(&self, shell: &str, arg: &str, cmd: &str) -> Command {
+        let mut command = Command::new("script");
+        if self.bsd {
+            command.arg("-q").arg(&self.path).args([shell, arg, cmd]);
+        } else {
+            command
+                .args(["-q", "-e", "-c"])
+                .arg(format!(
+                    "{shell} {arg} {}",
+                    shell_quote(cmd, Platform::Linux)
+                ))
+                .arg(&self.path);
+        }
+        command
+    }
+
+    /// Adds what the command printed to `tail`, minus the lines `script` adds itself.
+    pub fn record_into(&self, tail: &OutputTail) {
+        let Ok(bytes) = fs::read(&self.path) else {
+            return;
+        };
+        let output = String::from_utf8_lossy(&bytes);
+        let output: Vec<&str> = output
+            .lines()
+            .filter(|line| {
+                !line.starts_with("Script started on ") && !line.starts_with("Script done on ")
+            })
+            .collect();
+        tail.record(&output.join("\n"));
+    }
+}
+
+impl Drop for Transcript {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Creates a fresh directory under the temp dir that only this user can enter. `script`
+/// opens its transcript without `O_EXCL`, so a predictable path in a shared temp dir could be
+/// swapped for a symlink to another file; one inside a directory we just created can't.
+fn private_temp_dir() -> io::Result<PathBuf> {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or_default();
+    loop {
+        let dir = std::env::temp_dir().join(format!(
+            "tuihub-{}-{}-{nanos:x}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        match create_private_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Fails with `AlreadyExists` rather than reusing a directory someone else made.
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    fs::DirBuilder::new().mode(0o700).create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir(dir)
+}
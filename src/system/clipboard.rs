@@ -0,0 +1,55 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use super::os::Platform;
+
+/// Copies `text` with the platform's clipboard tool, trying each known one in turn.
+pub fn copy_to_clipboard(text: &str, platform: Platform) -> Result<()> {
+    let candidates: &[(&str, &[&str])] = match platform {
+        Platform::Mac => &[("pbcopy", &[])],
+        Platform::Windows | Platform::Wsl => &[("clip.exe", &[])],
+        _ => &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ],
+    };
+    let Some((program, args)) = candidates
+        .iter()
+        .find(|(program, _)| which::which(program).is_ok())
+    else {
+        let names = candidates
+            .iter()
+            .map(|(program, _)| *program)
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!("no clipboard tool found (tried {names})");
+    };
+    pipe_to(program, args, text)
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to run {program}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .with_context(|| format!("failed to write to {program}"))?;
+    }
+    let status = child
+        .wait()
+        .with_context(|| format!("failed to run {program}"))?;
+
+    if !status.success() {
+        anyhow::bail!("{program} failed with status {status}");
+    }
+
+    Ok(())
+}
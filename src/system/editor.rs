@@ -0,0 +1,52 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use super::os::Platform;
+
+/// `$VISUAL`, then `$EDITOR`, then the first of the usual editors found on PATH. The variable
+/// may carry arguments, e.g. `code --wait`.
+fn editor_command(platform: Platform) -> Option<Vec<String>> {
+    let from_env = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .map(|value| {
+            value
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .find(|words| !words.is_empty());
+    if from_env.is_some() {
+        return from_env;
+    }
+
+    let fallbacks: &[&str] = match platform {
+        Platform::Windows => &["notepad"],
+        _ => &["vi", "nano"],
+    };
+    fallbacks
+        .iter()
+        .find(|program| which::which(program).is_ok())
+        .map(|program| vec![program.to_string()])
+}
+
+/// Opens `path` in the user's editor and waits for it to exit.
+pub fn open_in_editor(path: &Path, platform: Platform) -> Result<()> {
+    let command = editor_command(platform).unwrap_or_default();
+    let Some((program, args)) = command.split_first() else {
+        anyhow::bail!("no editor found; set $EDITOR");
+    };
+    let status = Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .with_context(|| format!("failed to run {program}"))?;
+
+    if !status.success() {
+        anyhow::bail!("{program} exited with status {status}");
+    }
+
+    Ok(())
+}
@@ -1,17 +1,30 @@
-use std::process::{Command, Stdio};
+use std::collections::{BTreeMap, HashMap};
+use std::env::consts::ARCH;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{Context, Result};
-use which::which;
+use which::{which, which_in};
 
-use super::os::Platform;
-use crate::registry::model::InstallCommands;
+use super::capture::{OutputTail, Transcript};
+use super::os::{detect_package_manager, PkgMgr, Platform};
+use super::process::{in_own_group, kill_group, killed_by_ctrl_c, Foreground};
+use crate::registry::model::{AppEntry, ArchCommand, BinaryMatch, InstallCommands, LinuxCommand};
+use crate::utils::{extract_version, is_outdated};
 
 pub fn command_for_platform(commands: &InstallCommands, platform: Platform) -> Option<&str> {
     let cmd = match platform {
-        Platform::Linux => &commands.linux,
-        Platform::Wsl => &commands.wsl,
-        Platform::Mac => &commands.mac,
-        Platform::Windows => &commands.windows,
+        Platform::Linux => linux_command(&commands.linux)?,
+        Platform::Wsl => arch_command(&commands.wsl, ARCH)?,
+        Platform::Mac => arch_command(&commands.mac, ARCH)?,
+        Platform::Windows => arch_command(&commands.windows, ARCH)?,
         Platform::Unknown => return None,
     };
     if cmd.trim().is_empty() {
@@ -21,6 +34,149 @@ pub fn command_for_platform(commands: &InstallCommands, platform: Platform) -> O
     }
 }
 
+/// The entry's command for `platform` with `{id}`, `{binary}` and `{repo}` filled in.
+pub fn entry_command(
+    entry: &AppEntry,
+    commands: &InstallCommands,
+    platform: Platform,
+) -> Option<String> {
+    let cmd = command_for_platform(commands, platform)?;
+    let values = [
+        ("{id}", entry.id.as_str()),
+        ("{binary}", entry.binary.primary()),
+        ("{repo}", entry.repo.as_str()),
+    ];
+    // One pass over the template, so a value that itself contains `{id}` is left alone.
+    let mut expanded = String::with_capacity(cmd.len());
+    let mut rest = cmd;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                expanded.push_str(&shell_quote(value, platform));
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                expanded.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+/// Plain words go in as-is; anything else is quoted so a field can't add shell syntax.
+pub(crate) fn shell_quote(value: &str, platform: Platform) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@+=,".contains(c));
+    if plain {
+        return value.to_string();
+    }
+    if platform == Platform::Windows {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Architecture names in the spellings release assets tend to use, beside Rust's own.
+const ARCH_ALIASES: [(&str, &str); 2] = [("x86_64", "amd64"), ("aarch64", "arm64")];
+
+/// Keys an architecture-keyed command may use.
+pub const ARCH_KEYS: [&str; 10] = [
+    "x86",
+    "x86_64",
+    "amd64",
+    "arm",
+    "aarch64",
+    "arm64",
+    "riscv64",
+    "powerpc64",
+    "s390x",
+    "loongarch64",
+];
+
+/// Whether a catalog key names `arch`, as spelled by `std::env::consts::ARCH`.
+fn arch_matches(key: &str, arch: &str) -> bool {
+    key == arch
+        || ARCH_ALIASES
+            .iter()
+            .any(|(name, alias)| key == *alias && arch == *name)
+}
+
+fn arch_variant<'a>(variants: &'a BTreeMap<String, String>, arch: &str) -> Option<&'a str> {
+    variants
+        .iter()
+        .find(|(key, _)| arch_matches(key, arch))
+        .map(|(_, cmd)| cmd.as_str())
+}
+
+/// The variant for `arch` (normally `std::env::consts::ARCH`), falling back to `default`.
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use tuihub::registry::model::ArchCommand;
+/// use tuihub::system::exec::arch_command;
+///
+/// let by_arch = ArchCommand::ByArch(BTreeMap::from([
+///     ("x86_64".to_string(), "get fzf-amd64".to_string()),
+///     ("arm64".to_string(), "get fzf-arm64".to_string()),
+///     ("default".to_string(), "go install fzf".to_string()),
+/// ]));
+/// assert_eq!(arch_command(&by_arch, "x86_64"), Some("get fzf-amd64"));
+/// assert_eq!(arch_command(&by_arch, "aarch64"), Some("get fzf-arm64"));
+/// assert_eq!(arch_command(&by_arch, "riscv64"), Some("go install fzf"));
+///
+/// let no_default = ArchCommand::ByArch(BTreeMap::from([(
+///     "aarch64".to_string(),
+///     "get fzf-arm64".to_string(),
+/// )]));
+/// assert_eq!(arch_command(&no_default, "x86_64"), None);
+///
+/// let plain = ArchCommand::Single("brew install fzf".to_string());
+/// assert_eq!(arch_command(&plain, "aarch64"), Some("brew install fzf"));
+/// ```
+pub fn arch_command<'a>(command: &'a ArchCommand, arch: &str) -> Option<&'a str> {
+    match command {
+        ArchCommand::Single(cmd) => Some(cmd),
+        ArchCommand::ByArch(variants) => {
+            arch_variant(variants, arch).or_else(|| variants.get("default").map(String::as_str))
+        }
+    }
+}
+
+/// Picks the variant for the detected package manager, then any other manager present on
+/// this machine, then this machine's architecture, then the `default` key.
+fn linux_command(command: &LinuxCommand) -> Option<&str> {
+    let variants = match command {
+        LinuxCommand::Single(cmd) => return Some(cmd),
+        LinuxCommand::ByManager(variants) => variants,
+    };
+    let variant = |mgr: PkgMgr| {
+        variants
+            .iter()
+            .find(|(key, _)| PkgMgr::from_name(key) == Some(mgr))
+            .map(|(_, cmd)| cmd.as_str())
+    };
+    detect_package_manager()
+        .and_then(variant)
+        .or_else(|| {
+            variants
+                .iter()
+                .find(|(key, _)| PkgMgr::from_name(key).is_some_and(|mgr| mgr.is_available()))
+                .map(|(_, cmd)| cmd.as_str())
+        })
+        .or_else(|| arch_variant(variants, ARCH))
+        .or_else(|| variants.get("default").map(String::as_str))
+}
+
 pub fn shell_for_platform(platform: Platform) -> (&'static str, &'static str) {
     match platform {
         Platform::Windows => ("cmd", "/C"),
@@ -28,24 +184,769 @@ pub fn shell_for_platform(platform: Platform) -> (&'static str, &'static str) {
     }
 }
 
+/// Whether the entry's binaries are on PATH: all of them, or any one with `binary_match: any`.
+pub fn binaries_installed(entry: &AppEntry) -> bool {
+    let mut binaries = entry.binary.all().iter();
+    match entry.binary_match {
+        BinaryMatch::All => binaries.all(|binary| is_binary_installed(binary)),
+        BinaryMatch::Any => binaries.any(|binary| is_binary_installed(binary)),
+    }
+}
+
 pub fn is_binary_installed(binary: &str) -> bool {
-    which(binary).is_ok()
+    is_binary_on_path(binary, cfg!(windows), std::env::var_os("PATH"))
+}
+
+fn is_binary_on_path(binary: &str, windows: bool, path: Option<OsString>) -> bool {
+    binary_path_in(binary, windows, path).is_some()
+}
+
+/// Where `binary` is found on PATH, trying the same candidates as [`is_binary_installed`].
+fn binary_path(binary: &str) -> Option<PathBuf> {
+    binary_path_in(binary, cfg!(windows), std::env::var_os("PATH"))
+}
+
+fn binary_path_in(binary: &str, windows: bool, path: Option<OsString>) -> Option<PathBuf> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    binary_candidates(binary, windows)
+        .iter()
+        .find_map(|candidate| which_in(candidate, path.as_ref(), &cwd).ok())
+}
+
+/// Names to look up on PATH. Catalogs usually omit the extension, and on Windows tools are
+/// often installed as `.cmd`/`.bat`/`.ps1` shims that PATHEXT lookups can miss.
+fn binary_candidates(binary: &str, windows: bool) -> Vec<String> {
+    let has_extension = std::path::Path::new(binary).extension().is_some();
+    if !windows || has_extension {
+        return vec![binary.to_string()];
+    }
+    std::iter::once(binary.to_string())
+        .chain(
+            ["exe", "cmd", "bat", "ps1"]
+                .iter()
+                .map(|ext| format!("{binary}.{ext}")),
+        )
+        .collect()
+}
+
+/// How long a `version_command` may run before it is killed and the version counted as
+/// unknown.
+const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Runs the entry's `version_command` and extracts the version it prints. A command that
+/// outlives [`VERSION_TIMEOUT`] is killed, along with anything it started.
+pub fn installed_version(entry: &AppEntry, platform: Platform) -> Option<String> {
+    let cmd = entry
+        .version_command
+        .as_deref()
+        .filter(|cmd| !cmd.trim().is_empty())?;
+    let (shell, arg) = shell_for_platform(platform);
+    let output = output_within(Command::new(shell).arg(arg).arg(cmd), VERSION_TIMEOUT)?;
+    // Some tools print their version on stderr.
+    extract_version(&String::from_utf8_lossy(&output.stdout))
+        .or_else(|| extract_version(&String::from_utf8_lossy(&output.stderr)))
+}
+
+/// The installed version, if it is older than the catalog's `version`.
+pub fn outdated_version(entry: &AppEntry, platform: Platform) -> Option<String> {
+    let latest = entry.version.as_deref()?;
+    let installed = installed_version(entry, platform)?;
+    is_outdated(&installed, latest).then_some(installed)
+}
+
+/// How long a `verify` command may run before it is killed and counted as failing.
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Runs an entry's `verify` check. It runs on every refresh, so it skips the login shell's
+/// profile and is killed, along with anything it started, if it outlives [`VERIFY_TIMEOUT`].
+pub fn run_verify_cmd(cmd: &str, platform: Platform) -> bool {
+    let (shell, arg) = match platform {
+        Platform::Windows => ("cmd", "/C"),
+        _ => ("sh", "-c"),
+    };
+    succeeds_within(Command::new(shell).arg(arg).arg(cmd), VERIFY_TIMEOUT)
+}
+
+/// Runs `command` silently and reports whether it exited successfully within `timeout`.
+/// Past that it is killed along with anything it started and counts as failing.
+fn succeeds_within(command: &mut Command, timeout: Duration) -> bool {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let Ok(mut child) = in_own_group(command).spawn() else {
+        return false;
+    };
+    wait_within(&mut child, timeout).is_some_and(|status| status.success())
+}
+
+/// Like [`succeeds_within`], but keeps what the command printed. `None` if it couldn't be
+/// started or was killed at the deadline.
+fn output_within(command: &mut Command, timeout: Duration) -> Option<Output> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = in_own_group(command).spawn().ok()?;
+    let stdout = child.stdout.take().map(read_to_end_in_background);
+    let stderr = child.stderr.take().map(read_to_end_in_background);
+    let status = wait_within(&mut child, timeout)?;
+    let drain_deadline = Instant::now() + DRAIN_GRACE;
+    Some(Output {
+        status,
+        stdout: drained(stdout, drain_deadline),
+        stderr: drained(stderr, drain_deadline),
+    })
+}
+
+/// Waits for `child` to exit. Past `timeout` it is killed along with anything it started.
+fn wait_within(child: &mut Child, timeout: Duration) -> Option<ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+            _ => {
+                kill_group(child);
+                return None;
+            }
+        }
+    }
+}
+
+fn read_to_end_in_background(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// What a reader collected, or nothing if a process the command left behind still holds
+/// the pipe open at `deadline`.
+fn drained(reader: Option<JoinHandle<Vec<u8>>>, deadline: Instant) -> Vec<u8> {
+    let Some(reader) = reader else {
+        return Vec::new();
+    };
+    while !reader.is_finished() && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+    if reader.is_finished() {
+        reader.join().unwrap_or_default()
+    } else {
+        Vec::new()
+    }
+}
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Routes Ctrl-C to a flag so a running command can be killed without taking TUIHub down
+/// with it while the terminal is handed over to the child.
+pub fn install_interrupt_handler() -> Result<()> {
+    ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst))
+        .context("failed to install Ctrl-C handler")
+}
+
+pub fn take_interrupt() -> bool {
+    INTERRUPTED.swap(false, Ordering::SeqCst)
+}
+
+pub fn install_timeout() -> Option<Duration> {
+    std::env::var("TUIHUB_INSTALL_TIMEOUT")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+}
+
+/// Extra attempts for a failed install: the entry's `retries`, else `TUIHUB_INSTALL_RETRIES`.
+pub fn install_retries(entry: &AppEntry) -> u32 {
+    entry.retries.unwrap_or_else(|| {
+        std::env::var("TUIHUB_INSTALL_RETRIES")
+            .ok()
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .unwrap_or(0)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallState {
+    Installed,
+    /// The binary is on PATH but the entry's `verify` command rejected it.
+    Conflict,
+    Missing,
+}
+
+impl InstallState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InstallState::Installed => "installed",
+            InstallState::Conflict => "conflict",
+            InstallState::Missing => "available",
+        }
+    }
+}
+
+pub fn detect_install_state(entry: &AppEntry, platform: Platform) -> InstallState {
+    install_state_with(entry, |cmd| run_verify_cmd(cmd, platform))
+}
+
+/// Like [`detect_install_state`], but reuses `verify` results from `cache`.
+pub fn detect_install_state_cached(
+    entry: &AppEntry,
+    platform: Platform,
+    cache: &mut VerifyCache,
+) -> InstallState {
+    install_state_with(entry, |cmd| cache.passes(entry, cmd, platform))
+}
+
+fn install_state_with(entry: &AppEntry, mut verify: impl FnMut(&str) -> bool) -> InstallState {
+    if !binaries_installed(entry) {
+        return InstallState::Missing;
+    }
+    match entry.verify.as_deref() {
+        Some(cmd) if !cmd.trim().is_empty() && !verify(cmd) => InstallState::Conflict,
+        _ => InstallState::Installed,
+    }
+}
+
+/// `verify` results by the binary they checked, so refreshing every entry's state doesn't
+/// re-run each check. The key includes the binary's modification time, so a binary
+/// replaced in place is checked again.
+#[derive(Default)]
+pub struct VerifyCache {
+    results: HashMap<(String, PathBuf, Option<SystemTime>, Platform), bool>,
+}
+
+impl VerifyCache {
+    fn passes(&mut self, entry: &AppEntry, cmd: &str, platform: Platform) -> bool {
+        let Some(binary) = binary_path(entry.binary.primary()) else {
+            return run_verify_cmd(cmd, platform);
+        };
+        let modified = fs::metadata(&binary).and_then(|meta| meta.modified()).ok();
+        *self
+            .results
+            .entry((cmd.to_string(), binary, modified, platform))
+            .or_insert_with(|| run_verify_cmd(cmd, platform))
+    }
+}
+
+/// Extra environment for an entry's commands: the global `[env]` table from the config,
+/// overridden by the entry's own `env`, with `${VAR}` expanded from the current environment.
+pub fn command_env(entry: &AppEntry, global: &BTreeMap<String, String>) -> Vec<(String, String)> {
+    let mut merged = global.clone();
+    merged.extend(entry.env.clone());
+    merged
+        .into_iter()
+        .map(|(key, value)| (key, expand_env(&value)))
+        .collect()
+}
+
+/// Replaces `${VAR}` with the variable's value, or nothing if it is unset.
+fn expand_env(value: &str) -> String {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        let name = &rest[start + 2..start + 2 + len];
+        expanded.push_str(&std::env::var(name).unwrap_or_default());
+        rest = &rest[start + 3 + len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+pub fn run_install_cmd(
+    cmd: &str,
+    platform: Platform,
+    timeout: Option<Duration>,
+    env: &[(String, String)],
+) -> Result<()> {
+    take_interrupt();
+    run_cmd(cmd, platform, timeout, env, true, &INTERRUPTED)
 }
 
-pub fn run_install_cmd(cmd: &str, platform: Platform) -> Result<()> {
+/// Like [`run_install_cmd`], but with no stdin and nothing printed, so it can run while the
+/// TUI owns the screen. The output tail is still kept for failure reports.
+pub fn run_captured_cmd(
+    cmd: &str,
+    platform: Platform,
+    timeout: Option<Duration>,
+    env: &[(String, String)],
+) -> Result<()> {
+    take_interrupt();
+    run_cmd(cmd, platform, timeout, env, false, &INTERRUPTED)
+}
+
+/// How long a command's output readers may keep going after the command has exited.
+const DRAIN_GRACE: Duration = Duration::from_millis(500);
+
+fn run_cmd(
+    cmd: &str,
+    platform: Platform,
+    timeout: Option<Duration>,
+    env: &[(String, String)],
+    interactive: bool,
+    cancel: &AtomicBool,
+) -> Result<()> {
+    if let Some(tool) = missing_tool(cmd, platform) {
+        anyhow::bail!("required tool '{tool}' not found on PATH");
+    }
     let (shell, arg) = shell_for_platform(platform);
-    let status = Command::new(shell)
-        .arg(arg)
-        .arg(cmd)
-        .stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status()
+    // Interactive commands get the terminal itself, since tools that check isatty would
+    // otherwise hold back prompts and progress; their tail comes from a `script` transcript.
+    // The rest are piped and only their tail is kept.
+    let transcript = interactive.then(|| Transcript::new(platform)).flatten();
+    let mut command = match &transcript {
+        Some(transcript) => transcript.command(shell, arg, cmd),
+        None => {
+            let mut command = Command::new(shell);
+            command.arg(arg).arg(cmd);
+            command
+        }
+    };
+    command.envs(env.iter().map(|(key, value)| (key, value)));
+    if interactive {
+        command
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit());
+    } else {
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+    }
+    let mut child = in_own_group(&mut command)
+        .spawn()
         .with_context(|| format!("failed to execute install command: {cmd}"))?;
+    let foreground = interactive.then(|| Foreground::give_to(&child));
 
+    let tail = OutputTail::default();
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(tail.tee(stdout, io::sink()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(tail.tee(stderr, io::sink()));
+    }
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if cancel.swap(false, Ordering::SeqCst) {
+            kill_group(&mut child);
+            return Err(CommandCancelled.into());
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            kill_group(&mut child);
+            anyhow::bail!(
+                "command timed out after {}s",
+                timeout.unwrap_or_default().as_secs()
+            );
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    drop(foreground);
+
+    // A background process the command started can keep the pipes open long after it
+    // exits, so the readers only get a moment to drain before being left behind.
+    let drain_deadline = Instant::now() + DRAIN_GRACE;
+    for reader in readers {
+        while !reader.is_finished() && Instant::now() < drain_deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        if reader.is_finished() {
+            let _ = reader.join();
+        }
+    }
+    if let Some(transcript) = &transcript {
+        transcript.record_into(&tail);
+    }
+
+    if killed_by_ctrl_c(&status) {
+        return Err(CommandCancelled.into());
+    }
     if !status.success() {
-        anyhow::bail!("command failed with status {status}");
+        return Err(CommandFailed {
+            status,
+            output: tail.lines(),
+        }
+        .into());
     }
 
     Ok(())
 }
+
+/// Shell builtins and keywords that can start a command without being on PATH.
+const SHELL_BUILTINS: &[&str] = &[
+    "cd", "command", "echo", "eval", "exec", "exit", "export", "false", "for", "if", "printf",
+    "set", "source", "test", "true", "type", "while",
+];
+
+/// sudo options that take a value, which is not the program sudo runs.
+const SUDO_SHORT_VALUE_OPTIONS: &str = "ugChprtUDR";
+const SUDO_LONG_VALUE_OPTIONS: &[&str] = &[
+    "--user",
+    "--group",
+    "--close-from",
+    "--host",
+    "--prompt",
+    "--role",
+    "--type",
+    "--other-user",
+    "--chdir",
+    "--chroot",
+];
+
+/// The programs a command needs on PATH: the one it starts with and, when that is `sudo`, the
+/// one sudo runs. Builtins and words that aren't plain program names are left out. Always
+/// empty on Windows; cmd.exe has too many builtins to guess at.
+pub fn required_programs(cmd: &str, platform: Platform) -> Vec<String> {
+    if platform == Platform::Windows {
+        return Vec::new();
+    }
+    let mut words = command_words(cmd);
+    let Some(first) = words.next() else {
+        return Vec::new();
+    };
+    let mut programs = vec![first];
+    if first == "sudo" {
+        programs.extend(sudo_program(words));
+    }
+    programs
+        .into_iter()
+        .filter(|program| {
+            program
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | '+'))
+        })
+        .filter(|program| !SHELL_BUILTINS.contains(program))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The program a command runs, looking past leading `VAR=value` assignments and, for
+/// `sudo`, past sudo's own options. `None` for an empty command or a bare `sudo`.
+pub fn command_program(cmd: &str) -> Option<&str> {
+    let mut words = command_words(cmd);
+    match words.next()? {
+        "sudo" => sudo_program(words),
+        first => Some(first),
+    }
+}
+
+/// The words of a command, minus the `VAR=value` assignments it starts with.
+fn command_words(cmd: &str) -> impl Iterator<Item = &str> {
+    cmd.split_whitespace()
+        .skip_while(|word| is_assignment(word))
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.contains('=') && !word.starts_with('=') && !word.starts_with('-')
+}
+
+/// The program a `sudo` invocation runs, given the words after `sudo`.
+fn sudo_program<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    while let Some(word) = words.next() {
+        if word == "--" {
+            return words.next();
+        }
+        // sudo takes `VAR=value` assignments before the program, like a shell does.
+        if is_assignment(word) {
+            continue;
+        }
+        if !word.starts_with('-') {
+            return Some(word);
+        }
+        let takes_value = if word.starts_with("--") {
+            SUDO_LONG_VALUE_OPTIONS.contains(&word)
+        } else {
+            // `-u root` and `-Eu root` take the next word; in `-uroot` the value is attached.
+            let letters = &word[1..];
+            letters
+                .char_indices()
+                .find(|(_, c)| SUDO_SHORT_VALUE_OPTIONS.contains(*c))
+                .is_some_and(|(at, _)| at == letters.len() - 1)
+        };
+        if takes_value {
+            words.next();
+        }
+    }
+    None
+}
+
+/// How long the login shell gets to answer `command -v` for a program not on TUIHub's PATH.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Whether `program` can be run by an install command. Commands run in a login shell, whose
+/// profile may add to PATH (Homebrew, cargo, nvm), so a program TUIHub can't see itself is
+/// looked up there before it is reported missing.
+pub fn program_available(program: &str, platform: Platform) -> bool {
+    if which(program).is_ok() {
+        return true;
+    }
+    let (shell, arg) = shell_for_platform(platform);
+    platform != Platform::Windows
+        && succeeds_within(
+            Command::new(shell)
+                .arg(arg)
+                .arg(format!("command -v {}", shell_quote(program, platform))),
+            LOOKUP_TIMEOUT,
+        )
+}
+
+/// The first required program that isn't installed, so the user gets a clear error instead of
+/// the shell's "command not found".
+fn missing_tool(cmd: &str, platform: Platform) -> Option<String> {
+    required_programs(cmd, platform)
+        .into_iter()
+        .find(|program| !program_available(program, platform))
+}
+
+#[derive(Debug)]
+pub struct CommandFailed {
+    pub status: ExitStatus,
+    /// The last lines the command printed, stdout and stderr interleaved.
+    pub output: Vec<String>,
+}
+
+impl fmt::Display for CommandFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "command failed with status {}", self.status)
+    }
+}
+
+/// The captured output tail, if `err` came from a command that exited unsuccessfully.
+pub fn failure_output(err: &anyhow::Error) -> Option<&[String]> {
+    err.downcast_ref::<CommandFailed>()
+        .map(|failed| failed.output.as_slice())
+}
+
+impl std::error::Error for CommandFailed {}
+
+/// A command stopped by Ctrl-C. Batches end at the first one rather than moving on to the
+/// next app.
+#[derive(Debug)]
+pub struct CommandCancelled;
+
+impl fmt::Display for CommandCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("command cancelled")
+    }
+}
+
+impl std::error::Error for CommandCancelled {}
+
+pub fn is_cancelled(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<CommandCancelled>().is_some()
+}
+
+/// Only plain non-zero exits are worth another try. Cancellation, timeouts, signals and
+/// the "can't run this at all" codes (77 EX_NOPERM, 126 not executable, 127 not found)
+/// would fail the same way again.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<CommandFailed>()
+        .and_then(|failed| failed.status.code())
+        .is_some_and(|code| !matches!(code, 77 | 126 | 127))
+}
+
+/// Runs `cmd`, retrying transient failures up to `retries` more times with a doubling
+/// backoff. `on_retry` receives a note such as "attempt 2/3" before each retry.
+/// `interactive: false` runs it like [`run_captured_cmd`].
+pub fn run_install_cmd_with_retries(
+    cmd: &str,
+    platform: Platform,
+    timeout: Option<Duration>,
+    env: &[(String, String)],
+    retries: u32,
+    interactive: bool,
+    on_retry: impl FnMut(String),
+) -> Result<()> {
+    take_interrupt();
+    with_retries(retries, &INTERRUPTED, on_retry, || {
+        run_cmd(cmd, platform, timeout, env, interactive, &INTERRUPTED)
+    })
+}
+
+/// A non-interactive install for another thread, such as the install queue's worker.
+/// Ctrl-C is left to the event loop; setting `cancel` stops the command instead.
+pub fn run_install_in_background(
+    cmd: &str,
+    platform: Platform,
+    timeout: Option<Duration>,
+    env: &[(String, String)],
+    retries: u32,
+    cancel: &AtomicBool,
+    on_retry: impl FnMut(String),
+) -> Result<()> {
+    with_retries(retries, cancel, on_retry, || {
+        run_cmd(cmd, platform, timeout, env, false, cancel)
+    })
+}
+
+fn with_retries(
+    retries: u32,
+    cancel: &AtomicBool,
+    mut on_retry: impl FnMut(String),
+    mut run: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    let attempts = retries.saturating_add(1);
+    let mut attempt = 1;
+    loop {
+        match run() {
+            Err(e) if attempt < attempts && is_retryable(&e) => {
+                let backoff = Duration::from_secs(1 << (attempt - 1).min(5));
+                attempt += 1;
+                on_retry(format!(
+                    "{e}; retrying in {}s (attempt {attempt}/{attempts})",
+                    backoff.as_secs()
+                ));
+                if !sleep_unless_cancelled(backoff, cancel) {
+                    return Err(CommandCancelled.into());
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+fn sleep_unless_cancelled(duration: Duration, cancel: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if cancel.swap(false, Ordering::SeqCst) {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::model::test_entry;
+
+    #[test]
+    fn binary_candidates_add_windows_shims() {
+        assert_eq!(binary_candidates("fd", false), ["fd"]);
+        assert_eq!(
+            binary_candidates("fd", true),
+            ["fd", "fd.exe", "fd.cmd", "fd.bat", "fd.ps1"]
+        );
+        assert_eq!(binary_candidates("fd.exe", true), ["fd.exe"]);
+    }
+
+    #[test]
+    fn shim_on_fake_path_counts_as_installed_on_windows_only() {
+        let dir = std::env::temp_dir().join(format!("tuihub-test-path-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let shim = dir.join("faketool.cmd");
+        std::fs::write(&shim, "").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&shim, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let path = Some(dir.clone().into_os_string());
+
+        let on_windows = is_binary_on_path("faketool", true, path.clone());
+        let elsewhere = is_binary_on_path("faketool", false, path.clone());
+        let unrelated = is_binary_on_path("othertool", true, path);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(on_windows);
+        assert!(!elsewhere);
+        assert!(!unrelated);
+    }
+
+    #[test]
+    fn required_programs_skips_sudo_option_values() {
+        let programs = |cmd| required_programs(cmd, Platform::Linux);
+        assert_eq!(programs("sudo apt install -y fd"), ["sudo", "apt"]);
+        assert_eq!(programs("sudo -u root apt install fd"), ["sudo", "apt"]);
+        assert_eq!(programs("sudo -Eu root apt install fd"), ["sudo", "apt"]);
+        assert_eq!(programs("sudo -uroot apt install fd"), ["sudo", "apt"]);
+        assert_eq!(programs("sudo --user root dnf install fd"), ["sudo", "dnf"]);
+        assert_eq!(programs("sudo --user=root dnf install fd"), ["sudo", "dnf"]);
+        assert_eq!(programs("sudo -- -weird-name"), ["sudo", "-weird-name"]);
+        assert_eq!(programs("sudo -E"), ["sudo"]);
+        assert_eq!(
+            programs("sudo DEBIAN_FRONTEND=noninteractive apt install fd"),
+            ["sudo", "apt"]
+        );
+    }
+
+    #[test]
+    fn package_manager_is_found_past_sudo_options() {
+        assert_eq!(
+            PkgMgr::from_command("sudo -u root apt install fd"),
+            Some(PkgMgr::Apt)
+        );
+        assert_eq!(
+            PkgMgr::from_command("FOO=1 sudo -E dnf install fd"),
+            Some(PkgMgr::Dnf)
+        );
+        assert_eq!(PkgMgr::from_command("sudo -g wheel"), None);
+    }
+
+    #[test]
+    fn required_programs_skips_env_assignments_and_builtins() {
+        assert_eq!(
+            required_programs("FOO=1 cargo install fd-find", Platform::Linux),
+            ["cargo"]
+        );
+        assert!(required_programs("echo hi", Platform::Linux).is_empty());
+        assert!(required_programs("winget install fd", Platform::Windows).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn verify_cache_runs_each_check_once_per_binary() {
+        let counter =
+            std::env::temp_dir().join(format!("tuihub-test-verify-{}", std::process::id()));
+        let entry = test_entry(
+            "cached",
+            serde_json::json!({
+                "binary": "sh",
+                "verify": format!("echo run >> {}", counter.display()),
+            }),
+        );
+        let mut cache = VerifyCache::default();
+        let first = detect_install_state_cached(&entry, Platform::Linux, &mut cache);
+        let second = detect_install_state_cached(&entry, Platform::Linux, &mut cache);
+        let runs = std::fs::read_to_string(&counter).unwrap_or_default();
+        let _ = std::fs::remove_file(&counter);
+
+        assert_eq!(
+            (first, second),
+            (InstallState::Installed, InstallState::Installed)
+        );
+        assert_eq!(runs.lines().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_within_keeps_output_and_gives_up_at_the_deadline() {
+        let sh = |script| {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(script);
+            command
+        };
+        let output = output_within(&mut sh("echo 1.2.3; echo oops >&2"), VERIFY_TIMEOUT).unwrap();
+        assert_eq!(output.stdout, b"1.2.3\n");
+        assert_eq!(output.stderr, b"oops\n");
+
+        let started = Instant::now();
+        assert!(
+            output_within(&mut sh("echo 1.2.3; sleep 30"), Duration::from_millis(100)).is_none()
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,4 +1,10 @@
+pub mod browser;
+pub mod capture;
+pub mod clipboard;
+pub mod editor;
 pub mod exec;
 pub mod os;
+pub mod paths;
+pub mod process;
 pub mod tmux;
-
+pub mod watch;
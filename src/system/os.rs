@@ -1,6 +1,9 @@
 use std::fs;
+use std::sync::OnceLock;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use super::exec::command_program;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Platform {
     Linux,
     Wsl,
@@ -26,6 +29,16 @@ impl Platform {
         Self::Unknown
     }
 
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "linux" => Some(Self::Linux),
+            "wsl" => Some(Self::Wsl),
+            "mac" | "macos" => Some(Self::Mac),
+            "windows" => Some(Self::Windows),
+            _ => None,
+        }
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             Platform::Linux => "Linux",
@@ -58,3 +71,97 @@ pub fn platform_label(platform: Platform) -> &'static str {
         Platform::Unknown => "Unknown",
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PkgMgr {
+    Apt,
+    Dnf,
+    Pacman,
+    Zypper,
+    Apk,
+    Brew,
+    Winget,
+    Scoop,
+    Choco,
+}
+
+impl PkgMgr {
+    /// Probe order matters: a distro manager wins over a Homebrew install living next to it.
+    const ALL: [PkgMgr; 9] = [
+        Self::Apt,
+        Self::Dnf,
+        Self::Pacman,
+        Self::Zypper,
+        Self::Apk,
+        Self::Brew,
+        Self::Winget,
+        Self::Scoop,
+        Self::Choco,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PkgMgr::Apt => "apt",
+            PkgMgr::Dnf => "dnf",
+            PkgMgr::Pacman => "pacman",
+            PkgMgr::Zypper => "zypper",
+            PkgMgr::Apk => "apk",
+            PkgMgr::Brew => "brew",
+            PkgMgr::Winget => "winget",
+            PkgMgr::Scoop => "scoop",
+            PkgMgr::Choco => "choco",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "apt" | "apt-get" => Some(Self::Apt),
+            "dnf" | "yum" => Some(Self::Dnf),
+            "pacman" => Some(Self::Pacman),
+            "zypper" => Some(Self::Zypper),
+            "apk" => Some(Self::Apk),
+            "brew" => Some(Self::Brew),
+            "winget" => Some(Self::Winget),
+            "scoop" => Some(Self::Scoop),
+            "choco" => Some(Self::Choco),
+            _ => None,
+        }
+    }
+
+    /// The manager an install command runs, parsed the same way as
+    /// [`required_programs`](super::exec::required_programs): past env assignments, `sudo`
+    /// and sudo's options, so `sudo -u root apt install` is apt.
+    pub fn from_command(cmd: &str) -> Option<Self> {
+        command_program(cmd).and_then(Self::from_name)
+    }
+
+    /// Whether the manager's binary is on PATH. Looked up once per manager: the app list
+    /// asks for every row on every frame.
+    pub fn is_available(&self) -> bool {
+        static AVAILABLE: [OnceLock<bool>; PkgMgr::ALL.len()] =
+            [const { OnceLock::new() }; PkgMgr::ALL.len()];
+        *AVAILABLE[*self as usize].get_or_init(|| which::which(self.name()).is_ok())
+    }
+}
+
+pub fn detect_package_manager() -> Option<PkgMgr> {
+    static DETECTED: OnceLock<Option<PkgMgr>> = OnceLock::new();
+    *DETECTED.get_or_init(|| PkgMgr::ALL.into_iter().find(PkgMgr::is_available))
+}
+
+/// Warning text when `cmd` relies on a package manager this machine doesn't have.
+pub fn package_manager_mismatch(cmd: &str) -> Option<String> {
+    let wanted = PkgMgr::from_command(cmd)?;
+    let detected = detect_package_manager();
+    if detected == Some(wanted) || wanted.is_available() {
+        return None;
+    }
+    Some(match detected {
+        Some(found) => format!(
+            "uses {} but this system has {}",
+            wanted.name(),
+            found.name()
+        ),
+        None => format!("uses {} but it was not found on PATH", wanted.name()),
+    })
+}
@@ -0,0 +1,39 @@
+use std::env;
+use std::path::PathBuf;
+
+fn env_path(key: &str) -> Option<PathBuf> {
+    env::var_os(key)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env_path("HOME").or_else(|| env_path("USERPROFILE"))
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return env_path("APPDATA").map(|dir| dir.join("tuihub"));
+    }
+    env_path("XDG_CONFIG_HOME")
+        .or_else(|| home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("tuihub"))
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return env_path("LOCALAPPDATA").map(|dir| dir.join("tuihub").join("cache"));
+    }
+    env_path("XDG_CACHE_HOME")
+        .or_else(|| home_dir().map(|home| home.join(".cache")))
+        .map(|dir| dir.join("tuihub"))
+}
+
+pub fn state_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return env_path("LOCALAPPDATA").map(|dir| dir.join("tuihub"));
+    }
+    env_path("XDG_STATE_HOME")
+        .or_else(|| home_dir().map(|home| home.join(".local").join("state")))
+        .map(|dir| dir.join("tuihub"))
+}
@@ -0,0 +1,112 @@
+use std::process::{Child, Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a process group gets to exit after SIGTERM before it is sent SIGKILL.
+const TERM_GRACE: Duration = Duration::from_secs(2);
+
+/// Makes the child the leader of a new process group, so everything it starts (`sudo apt`,
+/// `curl | sh`) can be stopped together with [`kill_group`].
+pub fn in_own_group(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command
+}
+
+/// Stops the child and whatever it started: SIGTERM to its process group, then SIGKILL to
+/// anything still running after a grace period. The child is reaped either way.
+#[cfg(unix)]
+pub fn kill_group(child: &mut Child) {
+    let pgid = child.id() as libc::pid_t;
+    // SAFETY: kill(2) with a negative pid only signals the group the child leads.
+    unsafe { libc::kill(-pgid, libc::SIGTERM) };
+    let deadline = Instant::now() + TERM_GRACE;
+    let mut reaped = false;
+    while Instant::now() < deadline {
+        reaped = reaped || matches!(child.try_wait(), Ok(Some(_)));
+        // Signal 0 only checks whether any member of the group is left.
+        if reaped && unsafe { libc::kill(-pgid, 0) } != 0 {
+            return;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    unsafe { libc::kill(-pgid, libc::SIGKILL) };
+    let _ = child.wait();
+}
+
+#[cfg(not(unix))]
+pub fn kill_group(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Whether the child died from a Ctrl-C typed while it had the terminal.
+pub fn killed_by_ctrl_c(status: &ExitStatus) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        // `sh -c` exits with 128 + SIGINT when the command it waits on is interrupted.
+        status.signal() == Some(libc::SIGINT) || status.code() == Some(128 + libc::SIGINT)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        false
+    }
+}
+
+/// Hands the terminal to an interactive child's process group, as a shell does for a
+/// foreground job: the child can read the tty without being stopped by SIGTTIN, and Ctrl-C
+/// reaches it rather than TUIHub. The terminal goes back to TUIHub's group on drop.
+/// Does nothing when stdin is not a terminal TUIHub owns.
+pub struct Foreground {
+    #[cfg(unix)]
+    previous: Option<libc::pid_t>,
+}
+
+impl Foreground {
+    #[cfg(unix)]
+    pub fn give_to(child: &Child) -> Self {
+        let fd = libc::STDIN_FILENO;
+        let pgid = child.id() as libc::pid_t;
+        // SAFETY: plain termios/signal calls on our own stdin and the child's group.
+        let previous = unsafe {
+            let current = libc::tcgetpgrp(fd);
+            if libc::isatty(fd) == 0 || current != libc::getpgrp() {
+                return Self { previous: None };
+            }
+            if libc::tcsetpgrp(fd, pgid) != 0 {
+                return Self { previous: None };
+            }
+            // The child may already have touched the tty and been stopped for it.
+            libc::kill(-pgid, libc::SIGCONT);
+            current
+        };
+        Self {
+            previous: Some(previous),
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn give_to(_child: &Child) -> Self {
+        Self {}
+    }
+}
+
+impl Drop for Foreground {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(previous) = self.previous {
+            // SAFETY: TUIHub is a background group at this point, so tcsetpgrp would raise
+            // SIGTTOU; it is ignored just for the call.
+            unsafe {
+                let old = libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+                libc::tcsetpgrp(libc::STDIN_FILENO, previous);
+                libc::signal(libc::SIGTTOU, old);
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use std::path::Path;
 use std::process::Command;
 
 use anyhow::{Context, Result};
 use chrono::Utc;
+use serde::Deserialize;
 
 use super::os::Platform;
 use crate::registry::model::AppEntry;
@@ -16,6 +18,39 @@ pub fn in_tmux_session() -> bool {
         .unwrap_or(false)
 }
 
+/// A `session:window` where `entry`'s binary is already running in some pane, if any.
+pub fn running_in_tmux(entry: &AppEntry) -> Option<String> {
+    let binary = Path::new(entry.binary.primary())
+        .file_name()?
+        .to_string_lossy();
+    let output = Command::new("tmux")
+        .args([
+            "list-panes",
+            "-a",
+            "-F",
+            "#{session_name}:#{window_index}\t#{pane_current_command}",
+        ])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .find(|(_, command)| *command == binary)
+        .map(|(target, _)| target.to_string())
+}
+
+/// Moves this tmux client to `target`. Only meaningful inside tmux.
+pub fn switch_to(target: &str) -> Result<()> {
+    let status = Command::new("tmux")
+        .args(["switch-client", "-t", target])
+        .status()
+        .context("failed to switch tmux client")?;
+    if !status.success() {
+        anyhow::bail!("failed to switch to {target} (status: {status})");
+    }
+    Ok(())
+}
+
 pub fn tmux_install_hint(platform: Platform) -> &'static str {
     match platform {
         Platform::Linux | Platform::Wsl => "Install tmux: sudo apt install tmux",
@@ -42,32 +77,176 @@ pub fn sanitize_tmux_name(input: &str) -> String {
     }
 }
 
-pub fn launch_in_tmux(entry: &AppEntry) -> Result<String> {
-    let timestamp = Utc::now().timestamp();
-    let safe_name = sanitize_tmux_name(&entry.id);
+/// Whether a launch opens a new tmux session or a window in the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TmuxTarget {
+    /// A window when TUIHub itself runs inside tmux, a detached session otherwise.
+    #[default]
+    Auto,
+    AlwaysSession,
+    /// Outside tmux this uses the most recent session, falling back to a new one when no
+    /// tmux server is running.
+    AlwaysWindow,
+}
 
-    if in_tmux_session() {
-        let window_name = format!("th-{safe_name}-{timestamp}");
-        let status = Command::new("tmux")
-            .args(["new-window", "-n", &window_name, &entry.binary])
-            .status()
-            .context("failed to create tmux window")?;
+impl TmuxTarget {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(TmuxTarget::Auto),
+            "always-session" | "session" => Some(TmuxTarget::AlwaysSession),
+            "always-window" | "window" => Some(TmuxTarget::AlwaysWindow),
+            _ => None,
+        }
+    }
 
-        if !status.success() {
-            anyhow::bail!("failed to create tmux window (status: {status})");
+    fn opens_window(self) -> bool {
+        match self {
+            TmuxTarget::Auto => in_tmux_session(),
+            TmuxTarget::AlwaysSession => false,
+            TmuxTarget::AlwaysWindow => in_tmux_session() || server_running(),
         }
+    }
+}
+
+fn server_running() -> bool {
+    Command::new("tmux")
+        .arg("has-session")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Returns `window:{name}` or `session:{name}`, whichever `policy` ended up opening.
+pub fn launch_in_tmux(entry: &AppEntry, policy: TmuxTarget) -> Result<String> {
+    if policy.opens_window() {
+        let window_name = open_window(WINDOW_TEMPLATE, entry)?;
         return Ok(format!("window:{window_name}"));
     }
+    let session_name = open_session(SESSION_TEMPLATE, entry)?;
+    Ok(format!("session:{session_name}"))
+}
 
-    let session_name = format!("tuihub-{safe_name}-{timestamp}");
+fn open_window(template: &str, entry: &AppEntry) -> Result<String> {
+    let window_name = unique_name(&render_name(&name_template(template), entry), window_exists);
     let status = Command::new("tmux")
-        .args(["new-session", "-d", "-s", &session_name, &entry.binary])
+        .args(["new-window", "-n", &window_name, entry.launch_command()])
         .status()
-        .context("failed to create tmux session")?;
+        .context("failed to create tmux window")?;
+    if !status.success() {
+        anyhow::bail!("failed to create tmux window (status: {status})");
+    }
+    Ok(window_name)
+}
 
+fn open_session(template: &str, entry: &AppEntry) -> Result<String> {
+    let session_name = unique_name(
+        &render_name(&name_template(template), entry),
+        session_exists,
+    );
+    let status = Command::new("tmux")
+        .args([
+            "new-session",
+            "-d",
+            "-s",
+            &session_name,
+            entry.launch_command(),
+        ])
+        .status()
+        .context("failed to create tmux session")?;
     if !status.success() {
         anyhow::bail!("failed to create tmux session (status: {status})");
     }
+    Ok(session_name)
+}
 
-    Ok(format!("session:{session_name}"))
+const SESSION_TEMPLATE: &str = "tuihub-{id}-{timestamp}";
+const WINDOW_TEMPLATE: &str = "th-{id}-{timestamp}";
+const GROUP_SESSION_TEMPLATE: &str = "tuihub-{id}-group-{timestamp}";
+const GROUP_WINDOW_TEMPLATE: &str = "th-{id}-group-{timestamp}";
+
+/// Placeholders understood in `TUIHUB_SESSION_TEMPLATE`.
+pub const NAME_PLACEHOLDERS: &[(&str, &str)] = &[
+    ("{id}", "App id"),
+    ("{name}", "App display name"),
+    ("{timestamp}", "Unix time of the launch"),
+];
+
+/// `TUIHUB_SESSION_TEMPLATE` if set, else the built-in default for this kind of target.
+fn name_template(default: &str) -> String {
+    std::env::var("TUIHUB_SESSION_TEMPLATE")
+        .ok()
+        .filter(|template| !template.trim().is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+fn render_name(template: &str, entry: &AppEntry) -> String {
+    sanitize_tmux_name(
+        &template
+            .replace("{id}", &entry.id)
+            .replace("{name}", &entry.name)
+            .replace("{timestamp}", &Utc::now().timestamp().to_string()),
+    )
+}
+
+/// Appends `-2`, `-3`, ... until `exists` no longer matches.
+fn unique_name(base: &str, exists: impl Fn(&str) -> bool) -> String {
+    if !exists(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !exists(candidate))
+        .unwrap_or_else(|| base.to_string())
+}
+
+fn session_exists(name: &str) -> bool {
+    // `=` asks for an exact match instead of tmux's prefix matching.
+    Command::new("tmux")
+        .args(["has-session", "-t", &format!("={name}")])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn window_exists(name: &str) -> bool {
+    Command::new("tmux")
+        .args(["list-windows", "-F", "#{window_name}"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|line| line == name)
+        })
+}
+
+/// Opens `entries` as tiled panes of a single tmux window, which is a new window or a
+/// detached session per `policy`. Returns the location and how many entries got a pane;
+/// later entries are left to the caller if a split fails.
+pub fn launch_split_in_tmux(entries: &[AppEntry], policy: TmuxTarget) -> Result<(String, usize)> {
+    let Some((first, rest)) = entries.split_first() else {
+        anyhow::bail!("nothing to launch");
+    };
+    let (target, location) = if policy.opens_window() {
+        let window_name = open_window(GROUP_WINDOW_TEMPLATE, first)?;
+        (window_name.clone(), format!("window:{window_name}"))
+    } else {
+        let session_name = open_session(GROUP_SESSION_TEMPLATE, first)?;
+        (session_name.clone(), format!("session:{session_name}"))
+    };
+
+    let mut opened = 1;
+    for entry in rest {
+        let split = Command::new("tmux")
+            .args(["split-window", "-t", &target, entry.launch_command()])
+            .status();
+        if !split.is_ok_and(|status| status.success()) {
+            break;
+        }
+        opened += 1;
+        // Re-tile after every split so tmux doesn't run out of room for the next pane.
+        let _ = Command::new("tmux")
+            .args(["select-layout", "-t", &target, "tiled"])
+            .status();
+    }
+
+    Ok((location, opened))
 }
@@ -0,0 +1,73 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub struct CatalogWatcher {
+    _watcher: RecommendedWatcher,
+    changes: Receiver<()>,
+}
+
+impl CatalogWatcher {
+    pub fn spawn(path: &Path) -> Result<Self> {
+        let target = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let dir = match target.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let file_name = target.file_name().map(|name| name.to_os_string());
+
+        let (raw_tx, raw_rx) = mpsc::channel::<notify::Result<Event>>();
+        let mut watcher =
+            notify::recommended_watcher(raw_tx).context("failed to create catalog watcher")?;
+        // Editors often replace the file instead of writing in place, so watch the directory.
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("failed to watch {}", dir.display()))?;
+
+        let (tx, changes) = mpsc::channel();
+        thread::spawn(move || {
+            let is_catalog_change = |event: &Event| {
+                !matches!(event.kind, EventKind::Access(_))
+                    && event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name)
+            };
+
+            while let Ok(event) = raw_rx.recv() {
+                if !event.map(|e| is_catalog_change(&e)).unwrap_or(false) {
+                    continue;
+                }
+                loop {
+                    match raw_rx.recv_timeout(DEBOUNCE) {
+                        Ok(_) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+                if tx.send(()).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Self {
+            _watcher: watcher,
+            changes,
+        })
+    }
+
+    pub fn changed(&self) -> bool {
+        let mut changed = false;
+        while self.changes.try_recv().is_ok() {
+            changed = true;
+        }
+        changed
+    }
+}
@@ -1,22 +1,53 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    style::{Color, Modifier, Style},
-    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
+    style::{Modifier, Style},
+    widgets::{
+        Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    },
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::app::state::App;
-use crate::ui::theme::*;
-use crate::utils::truncate_with_ellipsis;
+use crate::app::state::{App, ListRow, SortMode};
+use crate::registry::model::AppEntry;
+use crate::system::exec::command_for_platform;
+use crate::ui::glyphs::glyphs;
+use crate::ui::icons::{icon_for, ICON_WIDTH};
+use crate::ui::theme::Theme;
+use crate::utils::{
+    find_match_range, pad_to_width, truncate_at_word, truncate_with_ellipsis, SearchField,
+    SearchQuery,
+};
 
 pub fn render_app_list(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    let glyphs = glyphs();
+    let title = match app.sort_mode {
+        SortMode::Catalog => " Catalog ".to_string(),
+        mode => format!(" Catalog {} by {} ", glyphs.separator, mode.label()),
+    };
+    // Counts entries only, so category headers don't shift the position.
+    let position = match app.list_state.selected() {
+        Some(selected) if !app.filtered_indices.is_empty() => app
+            .list_rows
+            .iter()
+            .take(selected + 1)
+            .filter(|row| row.is_selectable())
+            .count(),
+        _ => 0,
+    };
     let catalog_block = Block::default()
-        .title(" Catalog ")
+        .title(title)
+        .title(
+            Line::from(format!(" {position}/{} ", app.filtered_indices.len()))
+                .right_aligned()
+                .style(Style::default().fg(theme.muted)),
+        )
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(C_PANEL))
-        .style(Style::default().bg(C_BG));
+        .border_set(glyphs.border)
+        .border_style(Style::default().fg(theme.panel))
+        .style(Style::default().bg(theme.bg));
     let catalog_inner = catalog_block.inner(area);
     frame.render_widget(catalog_block, area);
 
@@ -25,53 +56,167 @@ pub fn render_app_list(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
         .constraints([Constraint::Length(1), Constraint::Min(4)])
         .split(catalog_inner);
 
-    let header_line =
-        Paragraph::new("Sel  Name                 Category        State       Description")
-            .style(Style::default().fg(C_MUTED).add_modifier(Modifier::BOLD));
+    let icon_column = if app.show_icons { ICON_WIDTH + 1 } else { 0 };
+    let header_line = Paragraph::new(format!(
+        "Sel   {}Name                 Category        State        Description",
+        " ".repeat(icon_column)
+    ))
+    .style(
+        Style::default()
+            .fg(theme.muted)
+            .add_modifier(Modifier::BOLD),
+    );
     frame.render_widget(header_line, left_chunks[0]);
 
     let list_width = left_chunks[1].width as usize;
-    let desc_width = if list_width > 58 { list_width - 58 } else { 12 };
-    let items: Vec<ListItem> = app
-        .filtered_indices
-        .iter()
-        .filter_map(|idx| app.entries.get(*idx))
-        .map(|entry| {
-            let installed = app.is_installed(entry);
-            let selected = app.selected_ids.contains(&entry.id);
-            let checkbox = if selected { "[x]" } else { "[ ]" };
-            let install_badge = if installed { "installed" } else { "available" };
-            let display_name = truncate_with_ellipsis(&entry.name, 20);
-            let display_category = truncate_with_ellipsis(&entry.category, 14);
-            let display_desc = truncate_with_ellipsis(&entry.description, desc_width);
+    let fixed_width = 60 + icon_column;
+    let desc_width = if list_width > fixed_width {
+        list_width - fixed_width
+    } else {
+        12
+    };
+    let query = SearchQuery::parse(&app.search_input);
+    let match_range = |text: &str, field: SearchField| match &app.search_regex {
+        Some(regex) => regex.find(text).map(|m| (m.start(), m.end())),
+        None => find_match_range(text, query.highlight_term(field)),
+    };
+    let entry_item = |entry: &AppEntry| {
+        let installed = app.is_installed(entry);
+        let selected = app.selected_ids.contains(&entry.id);
+        let checkbox = if selected { "[x]" } else { "[ ]" };
+        let star = if app.is_favorite(entry) {
+            glyphs.favorite
+        } else {
+            " "
+        };
+        let conflict = app.is_conflict(entry);
+        let (install_badge, badge_color) = if installed && app.outdated.contains_key(&entry.id) {
+            ("outdated", theme.warning)
+        } else if installed {
+            ("installed", theme.success)
+        } else if conflict {
+            ("conflict", theme.danger)
+        } else if command_for_platform(&entry.install, app.platform).is_none() {
+            ("unavailable", theme.muted)
+        } else if app.is_new(entry) {
+            ("new", theme.primary)
+        } else {
+            ("available", theme.warning)
+        };
+        let display_name = truncate_with_ellipsis(&entry.name, 20, glyphs.ellipsis);
+        let display_category = truncate_with_ellipsis(&entry.category, 14, glyphs.ellipsis);
+        let display_desc = truncate_at_word(&entry.description, desc_width, glyphs.ellipsis);
 
-            let line = Line::from(vec![
-                Span::styled(format!("{:<4}", checkbox), Style::default().fg(C_PRIMARY)),
-                Span::styled(format!("{:<21}", display_name), Style::default().fg(C_TEXT)),
-                Span::styled(
-                    format!("{:<16}", display_category),
-                    Style::default().fg(C_MUTED),
-                ),
-                Span::styled(
-                    format!("{:<11}", install_badge),
-                    Style::default().fg(if installed { C_SUCCESS } else { C_WARNING }),
-                ),
-                Span::styled(display_desc, Style::default().fg(C_TEXT)),
-            ]);
+        let mut spans = vec![
+            Span::styled(checkbox, Style::default().fg(theme.primary)),
+            Span::styled(format!("{star} "), Style::default().fg(theme.warning)),
+        ];
+        if app.show_icons {
+            // Wider icons are cut so a stray string can't push the columns out of line.
+            let icon = truncate_with_ellipsis(icon_for(entry), ICON_WIDTH, "");
+            spans.push(Span::raw(format!("{} ", pad_to_width(&icon, ICON_WIDTH))));
+        }
+        spans.extend(highlighted_spans(
+            &display_name,
+            match_range(&display_name, SearchField::Name),
+            21,
+            Style::default().fg(theme.text),
+            &theme,
+        ));
+        spans.push(Span::styled(
+            pad_to_width(&display_category, 16),
+            Style::default().fg(theme.category_color(&entry.category)),
+        ));
+        spans.push(Span::styled(
+            format!("{:<12}", install_badge),
+            Style::default().fg(badge_color),
+        ));
+        spans.extend(highlighted_spans(
+            &display_desc,
+            match_range(&display_desc, SearchField::Description),
+            0,
+            Style::default().fg(theme.text),
+            &theme,
+        ));
+        let line = Line::from(spans);
 
-            ListItem::new(line)
+        ListItem::new(line)
+    };
+    let items: Vec<ListItem> = app
+        .list_rows
+        .iter()
+        .filter_map(|row| match row {
+            ListRow::Header {
+                category,
+                count,
+                collapsed,
+            } => {
+                let marker = if *collapsed {
+                    glyphs.folded
+                } else {
+                    glyphs.unfolded
+                };
+                Some(ListItem::new(Line::from(Span::styled(
+                    format!("{marker} {category} ({count})"),
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                ))))
+            }
+            ListRow::Entry(idx) => app.entries.get(*idx).map(&entry_item),
         })
         .collect();
 
     let app_list = List::new(items)
         .highlight_style(
-            Style::default()
-                .bg(Color::Rgb(32, 57, 84))
-                .fg(C_TEXT)
-                .add_modifier(Modifier::BOLD),
+            theme.emphasize(
+                Style::default()
+                    .bg(theme.highlight)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
         )
         .highlight_symbol(">> ")
         .repeat_highlight_symbol(true);
 
     frame.render_stateful_widget(app_list, left_chunks[1], &mut app.list_state);
+    app.click_areas.list = left_chunks[1];
+
+    if app.list_rows.len() > left_chunks[1].height as usize {
+        let mut scrollbar_state = ScrollbarState::new(app.list_rows.len())
+            .position(app.list_state.selected().unwrap_or(0));
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .symbols(glyphs.scrollbar.clone())
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(Style::default().fg(theme.muted)),
+            left_chunks[1],
+            &mut scrollbar_state,
+        );
+    }
+}
+
+fn highlighted_spans(
+    text: &str,
+    range: Option<(usize, usize)>,
+    width: usize,
+    base: Style,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let Some((start, end)) = range.filter(|(start, end)| start < end) else {
+        return vec![Span::styled(pad_to_width(text, width), base)];
+    };
+    let padding = " ".repeat(width.saturating_sub(text.width()));
+
+    let highlight = theme.emphasize(
+        base.fg(theme.bg)
+            .bg(theme.warning)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+    );
+    vec![
+        Span::styled(text[..start].to_string(), base),
+        Span::styled(text[start..end].to_string(), highlight),
+        Span::styled(format!("{}{padding}", &text[end..]), base),
+    ]
 }
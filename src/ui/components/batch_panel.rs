@@ -0,0 +1,68 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::ui::glyphs::glyphs;
+use crate::ui::layout::centered_rect;
+
+pub fn render_batch_panel(frame: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(report) = app.batch_report.as_ref() else {
+        return;
+    };
+    let area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, area);
+
+    let failed = report.outcomes.iter().filter(|outcome| !outcome.ok).count();
+    let mut lines: Vec<Line> = report
+        .outcomes
+        .iter()
+        .map(|outcome| {
+            let (mark, color) = if outcome.ok {
+                (glyphs().ok, theme.success)
+            } else {
+                (glyphs().failed, theme.danger)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{mark} "),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{:<20} ", outcome.name),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(outcome.detail.clone(), Style::default().fg(theme.muted)),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} {}, {failed} {}. Press any key to close.",
+            report.outcomes.len() - failed,
+            report.tally_words.0,
+            report.tally_words.1
+        ),
+        Style::default().fg(theme.muted),
+    )));
+
+    let widget = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(format!(" {} ", report.title))
+            .borders(Borders::ALL)
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(if failed > 0 {
+                theme.danger
+            } else {
+                theme.success
+            }))
+            .style(Style::default().bg(theme.bg)),
+    );
+    frame.render_widget(widget, area);
+}
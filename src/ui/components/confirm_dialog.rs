@@ -0,0 +1,73 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::ui::glyphs::glyphs;
+use crate::ui::layout::centered_rect;
+
+pub fn render_confirm_dialog(frame: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(dialog) = app.confirm_dialog.as_ref() else {
+        return;
+    };
+    let area = centered_rect(50, 25, area);
+    frame.render_widget(Clear, area);
+
+    let block = Paragraph::new(dialog.message.as_str())
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .title(format!(" {} ", dialog.title))
+                .borders(Borders::ALL)
+                .border_set(glyphs().border)
+                .border_style(Style::default().fg(theme.panel)),
+        );
+    frame.render_widget(block, area);
+
+    let btn_area = Rect::new(
+        area.x + 2,
+        area.y + area.height - 3,
+        area.x + area.width - 2,
+        area.y + area.height - 1,
+    );
+
+    let yes_style = if app.confirm_selected {
+        theme.emphasize(
+            Style::default()
+                .fg(theme.bg)
+                .bg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Style::default()
+            .fg(theme.success)
+            .add_modifier(Modifier::BOLD)
+    };
+    let no_style = if !app.confirm_selected {
+        theme.emphasize(
+            Style::default()
+                .fg(theme.bg)
+                .bg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Style::default()
+            .fg(theme.warning)
+            .add_modifier(Modifier::BOLD)
+    };
+
+    let btns = Paragraph::new(vec![Line::from(vec![
+        Span::styled("[ Yes ] ", yes_style),
+        Span::styled("[ No ] ", no_style),
+    ])
+    .alignment(Alignment::Center)])
+    .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(btns, btn_area);
+}
@@ -1,102 +1,234 @@
+use chrono::Utc;
 use ratatui::{
     layout::Rect,
     prelude::*,
     style::{Modifier, Style},
-    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
     Frame,
 };
 
-use crate::app::state::App;
-use crate::system::exec::command_for_platform;
-use crate::ui::theme::*;
+use crate::app::state::{App, DetailSection, ListRow};
+use crate::registry::model::{
+    AppEntry, ArchCommand, Binaries, BinaryMatch, InstallCommands, LinuxCommand,
+};
+use crate::system::exec::{entry_command, is_binary_installed};
+use crate::system::os::package_manager_mismatch;
+use crate::ui::glyphs::glyphs;
+use crate::ui::theme::Theme;
+use crate::utils::humanize_since;
 
-pub fn render_detail_panel(frame: &mut Frame<'_>, area: Rect, app: &App) {
+pub fn render_detail_panel(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    let pinned = app.pinned_entry().is_some();
     let details_block = Block::default()
-        .title(" Details ")
+        .title(if pinned {
+            " Details [pinned, B to unpin] "
+        } else {
+            " Details "
+        })
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(C_PANEL))
-        .style(Style::default().bg(C_BG));
+        .border_set(glyphs().border)
+        .border_style(Style::default().fg(if pinned { theme.primary } else { theme.panel }))
+        .style(Style::default().bg(theme.bg));
     let details_inner = details_block.inner(area);
     frame.render_widget(details_block, area);
 
-    let details_lines = if let Some(entry) = app.current_entry() {
-        let install_cmd = command_for_platform(&entry.install, app.platform);
-        let uninstall_cmd = command_for_platform(&entry.uninstall, app.platform);
+    let details_lines = if let Some(entry) = app.detail_entry() {
+        let install_cmd = entry_command(entry, &entry.install, app.platform);
+        let uninstall_cmd = entry_command(entry, &entry.uninstall, app.platform);
         let installed = app.is_installed(entry);
+        let pkg_warning = install_cmd.as_deref().and_then(package_manager_mismatch);
+        let last_launched = app
+            .last_launched
+            .get(&entry.id)
+            .map(|at| {
+                let count = app.launch_count(entry);
+                let plural = if count == 1 { "" } else { "es" };
+                format!(
+                    "{} ({count} launch{plural})",
+                    humanize_since(*at, Utc::now())
+                )
+            })
+            .unwrap_or_else(|| "never".to_string());
 
-        let install_display = install_cmd
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "N/A".to_string());
-        let uninstall_display = uninstall_cmd
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "N/A".to_string());
+        let install_display = install_cmd.unwrap_or_else(|| "N/A".to_string());
+        let uninstall_display = uninstall_cmd.unwrap_or_else(|| "N/A".to_string());
 
-        vec![
+        let info = vec![
             Line::from(vec![
-                Span::styled("Name: ", Style::default().fg(C_MUTED)),
+                Span::styled("Name: ", Style::default().fg(theme.muted)),
                 Span::styled(
                     entry.name.clone(),
-                    Style::default().fg(C_TEXT).add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("ID: ", Style::default().fg(C_MUTED)),
-                Span::styled(entry.id.clone(), Style::default().fg(C_TEXT)),
-            ]),
-            Line::from(vec![
-                Span::styled("Category: ", Style::default().fg(C_MUTED)),
-                Span::styled(entry.category.clone(), Style::default().fg(C_TEXT)),
+                Span::styled("ID: ", Style::default().fg(theme.muted)),
+                Span::styled(entry.id.clone(), Style::default().fg(theme.text)),
             ]),
             Line::from(vec![
-                Span::styled("Platform: ", Style::default().fg(C_MUTED)),
-                Span::styled(app.platform.label(), Style::default().fg(C_TEXT)),
+                Span::styled("Category: ", Style::default().fg(theme.muted)),
+                Span::styled(entry.category.clone(), Style::default().fg(theme.text)),
             ]),
             Line::from(vec![
-                Span::styled("Installed: ", Style::default().fg(C_MUTED)),
+                Span::styled("Tags: ", Style::default().fg(theme.muted)),
                 Span::styled(
-                    if installed { "yes" } else { "no" },
-                    Style::default().fg(if installed { C_SUCCESS } else { C_WARNING }),
+                    if entry.tags.is_empty() {
+                        "-".to_string()
+                    } else {
+                        entry.tags.join(", ")
+                    },
+                    Style::default().fg(theme.text),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Binary: ", Style::default().fg(C_MUTED)),
-                Span::styled(entry.binary.clone(), Style::default().fg(C_TEXT)),
+                Span::styled("Platform: ", Style::default().fg(theme.muted)),
+                if app.platform_overridden {
+                    Span::styled(
+                        format!("{} (overridden)", app.platform.label()),
+                        Style::default()
+                            .fg(theme.warning)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Span::styled(app.platform.label(), Style::default().fg(theme.text))
+                },
+            ]),
+            Line::from(vec![
+                Span::styled("Installed: ", Style::default().fg(theme.muted)),
+                if installed {
+                    Span::styled("yes", Style::default().fg(theme.success))
+                } else if app.is_conflict(entry) {
+                    Span::styled(
+                        format!("conflict ('{}' is a different program)", entry.binary),
+                        Style::default().fg(theme.danger),
+                    )
+                } else {
+                    Span::styled("no", Style::default().fg(theme.warning))
+                },
             ]),
             Line::from(vec![
-                Span::styled("Repo: ", Style::default().fg(C_MUTED)),
-                Span::styled(entry.repo.clone(), Style::default().fg(C_PRIMARY)),
+                Span::styled("Last launched: ", Style::default().fg(theme.muted)),
+                Span::styled(last_launched, Style::default().fg(theme.text)),
             ]),
-            Line::from(""),
             Line::from(vec![
-                Span::styled("Install: ", Style::default().fg(C_MUTED)),
-                Span::styled(install_display, Style::default().fg(C_TEXT)),
+                Span::styled("Version: ", Style::default().fg(theme.muted)),
+                match (app.outdated.get(&entry.id), entry.version.as_deref()) {
+                    (Some(installed), Some(latest)) => Span::styled(
+                        format!("{installed} installed, {latest} available"),
+                        Style::default().fg(theme.warning),
+                    ),
+                    (None, Some(latest)) => {
+                        Span::styled(latest.to_string(), Style::default().fg(theme.text))
+                    }
+                    _ => Span::styled("-", Style::default().fg(theme.muted)),
+                },
+            ]),
+            binary_line(entry, theme),
+            Line::from(vec![
+                Span::styled("Launch: ", Style::default().fg(theme.muted)),
+                Span::styled(
+                    entry.launch_command().to_string(),
+                    Style::default().fg(theme.text),
+                ),
             ]),
             Line::from(vec![
-                Span::styled("Uninstall: ", Style::default().fg(C_MUTED)),
-                Span::styled(uninstall_display, Style::default().fg(C_TEXT)),
+                Span::styled("Repo: ", Style::default().fg(theme.muted)),
+                Span::styled(entry.repo.clone(), Style::default().fg(theme.primary)),
             ]),
-        ]
+            Line::from(vec![
+                Span::styled("Homepage: ", Style::default().fg(theme.muted)),
+                match entry.homepage.as_deref() {
+                    Some(homepage) => {
+                        Span::styled(homepage.to_string(), Style::default().fg(theme.primary))
+                    }
+                    None => Span::styled("-", Style::default().fg(theme.muted)),
+                },
+            ]),
+        ];
+        let description = vec![Line::from(Span::styled(
+            entry.description.clone(),
+            Style::default().fg(theme.text),
+        ))];
+        let mut commands = Vec::new();
+        if app.show_all_commands {
+            commands.extend(platform_command_lines("Install", &entry.install, theme));
+            commands.extend(platform_command_lines("Uninstall", &entry.uninstall, theme));
+            if let Some(post_install) = &entry.post_install {
+                commands.extend(platform_command_lines("Post-install", post_install, theme));
+            }
+        } else {
+            commands.push(Line::from(vec![
+                Span::styled("Install: ", Style::default().fg(theme.muted)),
+                Span::styled(install_display, Style::default().fg(theme.text)),
+            ]));
+            commands.push(Line::from(vec![
+                Span::styled("Uninstall: ", Style::default().fg(theme.muted)),
+                Span::styled(uninstall_display, Style::default().fg(theme.text)),
+            ]));
+        }
+        if let Some(warning) = pkg_warning {
+            commands.push(Line::from(Span::styled(
+                format!("Warning: install command {warning}"),
+                Style::default().fg(theme.warning),
+            )));
+        }
+        section_lines(
+            app,
+            [
+                (DetailSection::Info, info),
+                (DetailSection::Description, description),
+                (DetailSection::Commands, commands),
+            ],
+        )
     } else {
-        vec![Line::from(Span::styled(
-            "No apps match the current tab/filter/search.",
-            Style::default().fg(C_MUTED),
-        ))]
+        empty_reason_lines(app)
+    };
+
+    // The bottom row is reserved for the tip line.
+    let text_area = Rect {
+        height: details_inner.height.saturating_sub(1),
+        ..details_inner
     };
+    let width = text_area.width.max(1) as usize;
+    let content_height: usize = details_lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(width))
+        .sum();
+    let max_scroll = content_height.saturating_sub(text_area.height as usize) as u16;
+    app.detail_scroll = app.detail_scroll.min(max_scroll);
 
-    let details_widget = Paragraph::new(details_lines).wrap(Wrap { trim: true });
-    frame.render_widget(details_widget, details_inner);
+    let details_widget = Paragraph::new(details_lines)
+        .wrap(Wrap { trim: true })
+        .scroll((app.detail_scroll, 0));
+    frame.render_widget(details_widget, text_area);
+
+    if max_scroll > 0 {
+        let mut scrollbar_state =
+            ScrollbarState::new(max_scroll as usize).position(app.detail_scroll as usize);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .symbols(glyphs().scrollbar.clone())
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(Style::default().fg(theme.muted)),
+            text_area,
+            &mut scrollbar_state,
+        );
+    }
 
     let tip_line = Line::from(Span::styled(
         "Tip: Press q in tmux to return",
-        Style::default().fg(C_MUTED).add_modifier(Modifier::ITALIC),
+        Style::default()
+            .fg(theme.muted)
+            .add_modifier(Modifier::ITALIC),
     ));
     let tip_widget = Paragraph::new(tip_line)
-        .style(Style::default().fg(C_MUTED))
+        .style(Style::default().fg(theme.muted))
         .block(
             Block::default()
                 .borders(Borders::TOP)
-                .border_style(Style::default().fg(C_PANEL)),
+                .border_style(Style::default().fg(theme.panel)),
         );
     let tip_area = Rect::new(
         details_inner.x,
@@ -106,3 +238,210 @@ pub fn render_detail_panel(frame: &mut Frame<'_>, area: Rect, app: &App) {
     );
     frame.render_widget(tip_widget, tip_area);
 }
+
+/// Stacks the sections under fold headers, leaving out the body of collapsed ones. The
+/// header `[`/`]` focus is underlined.
+fn section_lines<const N: usize>(
+    app: &App,
+    sections: [(DetailSection, Vec<Line<'static>>); N],
+) -> Vec<Line<'static>> {
+    let theme = app.theme;
+    let mut lines = Vec::new();
+    for (section, body) in sections {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        let collapsed = app.collapsed_sections.contains(&section);
+        let marker = if collapsed {
+            glyphs().folded
+        } else {
+            glyphs().unfolded
+        };
+        let mut style = Style::default()
+            .fg(theme.primary)
+            .add_modifier(Modifier::BOLD);
+        if section == app.detail_focus {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        lines.push(Line::from(Span::styled(
+            format!("{marker} {}", section.label()),
+            style,
+        )));
+        if !collapsed {
+            lines.extend(body);
+        }
+    }
+    lines
+}
+
+/// Every platform's command as written in the catalog, placeholders unexpanded, with `—`
+/// where a platform has none.
+fn platform_command_lines(
+    label: &str,
+    commands: &InstallCommands,
+    theme: Theme,
+) -> Vec<Line<'static>> {
+    let row = |platform: String, cmd: &str| {
+        let cmd = if cmd.trim().is_empty() { "—" } else { cmd };
+        Line::from(vec![
+            Span::styled(format!("{platform:<17} "), Style::default().fg(theme.muted)),
+            Span::styled(cmd.to_string(), Style::default().fg(theme.text)),
+        ])
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!("{label}:"),
+        Style::default().fg(theme.muted),
+    ))];
+    match &commands.linux {
+        LinuxCommand::Single(cmd) => lines.push(row("linux".to_string(), cmd)),
+        LinuxCommand::ByManager(variants) if variants.is_empty() => {
+            lines.push(row("linux".to_string(), ""))
+        }
+        LinuxCommand::ByManager(variants) => lines.extend(
+            variants
+                .iter()
+                .map(|(manager, cmd)| row(format!("linux ({manager})"), cmd)),
+        ),
+    }
+    let platforms = [
+        ("wsl", &commands.wsl),
+        ("mac", &commands.mac),
+        ("windows", &commands.windows),
+    ];
+    for (platform, command) in platforms {
+        match command {
+            ArchCommand::Single(cmd) => lines.push(row(platform.to_string(), cmd)),
+            ArchCommand::ByArch(variants) if variants.is_empty() => {
+                lines.push(row(platform.to_string(), ""))
+            }
+            ArchCommand::ByArch(variants) => lines.extend(
+                variants
+                    .iter()
+                    .map(|(arch, cmd)| row(format!("{platform} ({arch})"), cmd)),
+            ),
+        }
+    }
+    lines
+}
+
+/// One binary is shown as is; a suite lists each binary with whether it is on PATH.
+fn binary_line(entry: &AppEntry, theme: Theme) -> Line<'static> {
+    let label = Span::styled("Binary: ", Style::default().fg(theme.muted));
+    let Binaries::Many(binaries) = &entry.binary else {
+        return Line::from(vec![
+            label,
+            Span::styled(entry.binary.to_string(), Style::default().fg(theme.text)),
+        ]);
+    };
+    let mut spans = vec![label];
+    for (i, binary) in binaries.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        let (mark, color) = if is_binary_installed(binary) {
+            (glyphs().ok, theme.success)
+        } else {
+            (glyphs().failed, theme.danger)
+        };
+        spans.push(Span::styled(
+            format!("{binary} {mark}"),
+            Style::default().fg(color),
+        ));
+    }
+    if entry.binary_match == BinaryMatch::Any {
+        spans.push(Span::styled(" (any)", Style::default().fg(theme.muted)));
+    }
+    Line::from(spans)
+}
+
+fn empty_reason_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = app.theme;
+
+    let focused_row = app
+        .list_state
+        .selected()
+        .and_then(|pos| app.list_rows.get(pos));
+    if let Some(ListRow::Header {
+        category, count, ..
+    }) = focused_row
+    {
+        return vec![Line::from(Span::styled(
+            format!(
+                "Category '{category}' is folded ({count} apps). Press Enter or Z to expand it."
+            ),
+            Style::default().fg(theme.muted),
+        ))];
+    }
+
+    let mut reasons = Vec::new();
+
+    match app.selected_tab {
+        1 if app.installed_ids.is_empty() => {
+            reasons.push(
+                "Nothing from the catalog is installed yet. Press I on an app to install it."
+                    .to_string(),
+            );
+        }
+        3 if app.is_checking_versions() => {
+            reasons.push("Checking installed versions...".to_string());
+        }
+        3 if !app.versions_checked => {
+            reasons.push("Versions haven't been checked yet. Press V to check them.".to_string());
+        }
+        3 if app.outdated.is_empty() => {
+            reasons.push("Everything installed is up to date.".to_string());
+        }
+        2 => {
+            let category = app
+                .categories
+                .get(app.selected_category)
+                .cloned()
+                .unwrap_or_default();
+            let total = app
+                .category_totals
+                .get(app.selected_category)
+                .copied()
+                .unwrap_or(0);
+            if total == 0 {
+                reasons.push(format!("Category '{category}' has no apps."));
+            } else {
+                reasons.push(format!(
+                    "Category '{category}' has {total} apps, but none pass the other filters."
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    if !app.search_input.trim().is_empty() {
+        reasons.push(format!(
+            "Search '{}' matches nothing here. Press Esc to clear it.",
+            app.search_input
+        ));
+    }
+
+    if app.hidden_unavailable > 0 {
+        reasons.push(format!(
+            "{} apps are hidden as unavailable on {}. Press Shift+A to show them.",
+            app.hidden_unavailable,
+            app.platform.label()
+        ));
+    }
+
+    if app.favorites_only {
+        reasons.push("Only pinned apps are shown. Press Shift+F to show all.".to_string());
+    }
+
+    if !app.active_tags.is_empty() {
+        reasons.push("Tag filter is active. Press T, then C to clear tags.".to_string());
+    }
+
+    if reasons.is_empty() {
+        reasons.push("No apps match the current tab/filter/search.".to_string());
+    }
+
+    reasons
+        .into_iter()
+        .map(|reason| Line::from(Span::styled(reason, Style::default().fg(theme.muted))))
+        .collect()
+}
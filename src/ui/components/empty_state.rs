@@ -0,0 +1,71 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::ui::glyphs::glyphs;
+
+const EXAMPLE_ENTRY: &str = r#"[
+  {
+    "id": "btop",
+    "name": "btop",
+    "description": "Resource monitor",
+    "category": "system",
+    "repo": "https://github.com/aristocratos/btop",
+    "binary": "btop",
+    "install": { "linux": "sudo apt install -y btop", "wsl": "sudo apt install -y btop", "mac": "brew install btop", "windows": "" },
+    "uninstall": { "linux": "sudo apt remove -y btop", "wsl": "sudo apt remove -y btop", "mac": "brew uninstall btop", "windows": "" }
+  }
+]"#;
+
+pub fn render_empty_state(frame: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme;
+    let block = Block::default()
+        .title(" Empty Catalog ")
+        .borders(Borders::ALL)
+        .border_set(glyphs().border)
+        .border_style(Style::default().fg(theme.panel))
+        .style(Style::default().bg(theme.bg));
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "No apps in the catalog yet.",
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "TUIHub reads its catalog from ",
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(
+                app.catalog_path.display().to_string(),
+                Style::default().fg(theme.primary),
+            ),
+            Span::styled(
+                ". Create that file with a JSON array of entries, for example:",
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    lines.extend(
+        EXAMPLE_ENTRY
+            .lines()
+            .map(|line| Line::from(Span::styled(line, Style::default().fg(theme.text)))),
+    );
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Then press R to reload, or Q to quit.",
+        Style::default().fg(theme.muted),
+    )));
+
+    let widget = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(block);
+    frame.render_widget(widget, area);
+}
@@ -2,62 +2,120 @@ use ratatui::{
     layout::Rect,
     prelude::*,
     style::{Modifier, Style},
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
 use crate::app::state::{App, LogLevel};
 use crate::system::os::platform_label;
-use crate::ui::theme::*;
+use crate::ui::glyphs::glyphs;
 
 pub fn render_footer(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
-    let now = std::time::Instant::now();
-    app.logs
-        .retain(|l| now.duration_since(l.created_at) < std::time::Duration::from_secs(3));
+    let theme = app.theme;
+    app.prune_logs();
 
     let installed_total = app.installed_ids.len();
     let selected_total = app.selected_ids.len();
     let visible_total = app.filtered_indices.len();
 
     let mut second_line: Vec<Span> = vec![
-        Span::styled("Actions ", Style::default().fg(C_MUTED)),
+        Span::styled("Actions ", Style::default().fg(theme.muted)),
         Span::styled(
             "Enter Quick Launch",
-            Style::default().fg(C_PRIMARY).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
         ),
         Span::styled("  ", Style::default()),
         Span::styled(
             "I Install",
-            Style::default().fg(C_SUCCESS).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
         ),
         Span::styled("  ", Style::default()),
         Span::styled(
             "L Launch",
-            Style::default().fg(C_PRIMARY).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
         ),
         Span::styled("  ", Style::default()),
         Span::styled(
             "U Uninstall",
-            Style::default().fg(C_WARNING).add_modifier(Modifier::BOLD),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
         ),
-        Span::styled("   |   ", Style::default().fg(C_PANEL)),
+        Span::styled("  ", Style::default()),
+        Span::styled(
+            "O Open Repo",
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("   |   ", Style::default().fg(theme.panel)),
         Span::styled(
             format!(
-                "visible:{} selected:{} installed:{} [{}]",
-                visible_total,
-                selected_total,
-                installed_total,
-                platform_label(app.platform)
+                "visible:{} selected:{} installed:{} ",
+                visible_total, selected_total, installed_total,
             ),
-            Style::default().fg(C_MUTED),
+            Style::default().fg(theme.muted),
         ),
     ];
+    // Up front, since the rest of the line is often cut off.
+    if let Some(summary) = app.install_queue.summary() {
+        second_line.insert(
+            0,
+            Span::styled(
+                format!("{summary}  "),
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    }
+    if app.show_hidden {
+        second_line.push(Span::styled(
+            "hidden entries shown ",
+            Style::default().fg(theme.warning),
+        ));
+    }
+    if app.favorites_only {
+        second_line.push(Span::styled(
+            "favorites only ",
+            Style::default().fg(theme.primary),
+        ));
+    }
+    if app.hide_unavailable {
+        second_line.push(Span::styled(
+            format!(
+                "{} hidden (unavailable on {}) ",
+                app.hidden_unavailable,
+                platform_label(app.platform)
+            ),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    second_line.push(if app.platform_overridden {
+        Span::styled(
+            format!("[{} OVERRIDE]", platform_label(app.platform)),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(
+            format!("[{}]", platform_label(app.platform)),
+            Style::default().fg(theme.muted),
+        )
+    });
 
     for l in &app.logs {
         let color = match l.level {
-            LogLevel::Success => C_SUCCESS,
-            LogLevel::Error => C_WARNING,
-            LogLevel::Info => C_PRIMARY,
+            LogLevel::Success => theme.success,
+            LogLevel::Error => theme.warning,
+            LogLevel::Info => theme.primary,
         };
         second_line.push(Span::styled("  ", Style::default()));
         second_line.push(Span::styled(l.message.clone(), Style::default().fg(color)));
@@ -65,50 +123,96 @@ pub fn render_footer(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
 
     let footer_lines = vec![
         Line::from(vec![
-            Span::styled("Move ", Style::default().fg(C_MUTED)),
+            Span::styled("Move ", Style::default().fg(theme.muted)),
             Span::styled(
-                "↑/↓ j/k",
-                Style::default().fg(C_TEXT).add_modifier(Modifier::BOLD),
+                format!("{} j/k", glyphs().up_down),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Tabs ", Style::default().fg(C_MUTED)),
+            Span::styled("  Details ", Style::default().fg(theme.muted)),
+            Span::styled(
+                "PgUp/PgDn",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Tabs ", Style::default().fg(theme.muted)),
             Span::styled(
                 "Tab/Shift+Tab",
-                Style::default().fg(C_TEXT).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Category ", Style::default().fg(C_MUTED)),
+            Span::styled("  Category ", Style::default().fg(theme.muted)),
             Span::styled(
-                "←/→",
-                Style::default().fg(C_TEXT).add_modifier(Modifier::BOLD),
+                glyphs().left_right,
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Search ", Style::default().fg(C_MUTED)),
+            Span::styled("  Search ", Style::default().fg(theme.muted)),
             Span::styled(
                 "/",
-                Style::default().fg(C_PRIMARY).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Tags ", Style::default().fg(theme.muted)),
+            Span::styled(
+                "T",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Select ", Style::default().fg(C_MUTED)),
+            Span::styled("  Select ", Style::default().fg(theme.muted)),
             Span::styled(
                 "Space",
-                Style::default().fg(C_TEXT).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Pin ", Style::default().fg(theme.muted)),
+            Span::styled(
+                "F",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Sort ", Style::default().fg(theme.muted)),
+            Span::styled(
+                "S",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Clear ", Style::default().fg(C_MUTED)),
+            Span::styled("  Clear ", Style::default().fg(theme.muted)),
             Span::styled(
                 "C",
-                Style::default().fg(C_TEXT).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Reload ", Style::default().fg(theme.muted)),
+            Span::styled(
+                "R",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Theme ", Style::default().fg(theme.muted)),
+            Span::styled(
+                "P",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  Help ", Style::default().fg(theme.muted)),
+            Span::styled(
+                "?",
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Quit ", Style::default().fg(C_MUTED)),
+            Span::styled("  Quit ", Style::default().fg(theme.muted)),
             Span::styled(
                 "Q",
-                Style::default().fg(C_TEXT).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(second_line),
     ];
+    let status_color = match app.status_level {
+        LogLevel::Success => theme.success,
+        LogLevel::Error => theme.danger,
+        LogLevel::Info => theme.muted,
+    };
     let footer = Paragraph::new(footer_lines).block(
         Block::default()
             .title(" Command Bar ")
+            .title_bottom(Span::styled(
+                format!(" {} ", app.status),
+                Style::default().fg(status_color),
+            ))
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(C_PANEL)),
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(theme.panel)),
     );
     frame.render_widget(footer, area);
 }
@@ -5,17 +5,18 @@ use ratatui::{
     Frame,
 };
 
-use crate::ui::theme::*;
+use crate::ui::glyphs::glyphs;
+use crate::ui::theme::Theme;
 
 #[allow(dead_code)]
-pub fn render_header(frame: &mut Frame<'_>, area: Rect) {
+pub fn render_header(frame: &mut Frame<'_>, area: Rect, theme: &Theme) {
     let title = Paragraph::new(" TUIHub ")
-        .style(Style::default().fg(C_TEXT))
+        .style(Style::default().fg(theme.text))
         .block(
             Block::default()
                 .borders(ratatui::widgets::Borders::ALL)
-                .border_type(ratatui::widgets::BorderType::Rounded)
-                .border_style(Style::default().fg(C_PANEL)),
+                .border_set(glyphs().border)
+                .border_style(Style::default().fg(theme.panel)),
         );
     frame.render_widget(title, area);
 }
@@ -0,0 +1,129 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::system::tmux::NAME_PLACEHOLDERS;
+use crate::ui::glyphs::glyphs;
+use crate::ui::layout::centered_rect;
+
+const KEYS: &[(&str, &str)] = &[
+    ("↑/↓ j/k", "Move"),
+    ("PgUp/PgDn", "Scroll details"),
+    ("Tab/Shift+Tab", "Switch tabs"),
+    ("1-4", "Jump to tab"),
+    ("←/→", "Switch category"),
+    ("Space", "Select app"),
+    ("C", "Clear selection"),
+    ("F", "Pin / unpin favorite"),
+    ("Shift+F", "Show only pinned apps, on every tab"),
+    ("N", "Mark apps new since the last run as seen"),
+    ("S", "Cycle sort: catalog, name, most launched"),
+    ("Enter", "Quick launch"),
+    ("I / U / L", "Install / Uninstall / Launch"),
+    (
+        "Shift+I",
+        "Queue installs in the background and keep browsing",
+    ),
+    ("Ctrl+C", "Cancel queued jobs (quits when none)"),
+    (
+        "Ctrl+P",
+        "Preflight: are install commands' programs on PATH?",
+    ),
+    ("Ctrl+Z", "Reinstall what the last uninstall removed"),
+    ("M", "Launch several apps as split panes or separately"),
+    ("Y", "Copy the tmux attach command for the last launch"),
+    ("B", "Pin the details to this app / unpin"),
+    ("X", "Show every platform's raw commands in details"),
+    ("[ / ]", "Focus the previous / next detail section"),
+    ("=", "Collapse or expand the focused detail section"),
+    ("O", "Open homepage (or repo) in browser"),
+    ("W", "Show where the binary lives"),
+    ("Shift+W", "Open the binary's directory in the file manager"),
+    ("E", "Output of the last failed command"),
+    ("T", "Tag filter"),
+    ("Shift+A", "Hide apps unavailable on this platform"),
+    ("Shift+H", "Reveal entries the catalog marks hidden"),
+    ("G", "Group the All tab by category"),
+    ("Z", "Fold / unfold the focused category"),
+    ("R", "Reload catalog"),
+    ("Ctrl+E", "Edit the catalog in $EDITOR, then reload it"),
+    ("V", "Check installed apps for updates"),
+    ("P", "Cycle theme"),
+    ("/", "Search"),
+    ("D", "Catalog stats"),
+    ("?", "Toggle this help"),
+    ("Q", "Quit"),
+];
+
+const SEARCH_SYNTAX: &[(&str, &str)] = &[
+    (
+        "lazy",
+        "Match name, id, aliases, category, tags or description",
+    ),
+    ("name:git", "Only match the name"),
+    ("id:btop", "Only match the id or aliases"),
+    ("cat:editor", "Only match the category"),
+    ("tag:dev", "Only match tags"),
+    ("desc:monitor", "Only match the description"),
+    (
+        "/^lazy(git)?$",
+        "Leading / searches all fields with a regex",
+    ),
+    ("↑/↓", "Recall earlier searches"),
+];
+
+pub fn render_help_panel(frame: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme;
+    let area = centered_rect(60, 80, area);
+    frame.render_widget(Clear, area);
+
+    let heading = |text: &'static str| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let row = |key: &'static str, desc: &'static str| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<16}", glyphs().keys(key)),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(desc, Style::default().fg(theme.muted)),
+        ])
+    };
+
+    let mut lines = vec![heading("Keys")];
+    lines.extend(KEYS.iter().map(|(key, desc)| row(key, desc)));
+    lines.push(Line::from(""));
+    lines.push(heading("Search syntax"));
+    lines.extend(SEARCH_SYNTAX.iter().map(|(key, desc)| row(key, desc)));
+    lines.push(Line::from(Span::styled(
+        "Field filters combine with each other and with free text.",
+        Style::default().fg(theme.muted),
+    )));
+    lines.push(Line::from(""));
+    lines.push(heading("tmux names (TUIHUB_SESSION_TEMPLATE)"));
+    lines.extend(NAME_PLACEHOLDERS.iter().map(|(key, desc)| row(key, desc)));
+    lines.push(Line::from(Span::styled(
+        "e.g. TUIHUB_SESSION_TEMPLATE={id}. Taken names get -2, -3, ...",
+        Style::default().fg(theme.muted),
+    )));
+
+    let widget = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Help (Esc to close) ")
+            .borders(Borders::ALL)
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(theme.primary))
+            .style(Style::default().bg(theme.bg)),
+    );
+    frame.render_widget(widget, area);
+}
@@ -1,13 +1,11 @@
 use ratatui::{layout::Rect, prelude::*, widgets::Paragraph, Frame};
 
 use crate::app::state::{App, LogLevel};
-use crate::ui::theme::*;
 
 #[allow(dead_code)]
 pub fn render_log_panel(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
-    let now = std::time::Instant::now();
-    app.logs
-        .retain(|l| now.duration_since(l.created_at) < std::time::Duration::from_secs(3));
+    let theme = app.theme;
+    app.prune_logs();
 
     if app.logs.is_empty() {
         return;
@@ -18,9 +16,9 @@ pub fn render_log_panel(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
         .iter()
         .map(|log| {
             let color = match log.level {
-                LogLevel::Success => C_SUCCESS,
-                LogLevel::Error => C_WARNING,
-                LogLevel::Info => C_PRIMARY,
+                LogLevel::Success => theme.success,
+                LogLevel::Error => theme.warning,
+                LogLevel::Info => theme.primary,
             };
             Line::from(Span::styled(
                 log.message.clone(),
@@ -29,7 +27,7 @@ pub fn render_log_panel(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
         })
         .collect();
 
-    let log_widget = Paragraph::new(log_lines).style(Style::default().fg(C_TEXT));
+    let log_widget = Paragraph::new(log_lines).style(Style::default().fg(theme.text));
 
     frame.render_widget(log_widget, area);
 }
@@ -1,6 +1,14 @@
 pub mod app_list;
+pub mod batch_panel;
+pub mod confirm_dialog;
 pub mod detail_panel;
+pub mod empty_state;
 pub mod footer;
 pub mod header;
+pub mod help_panel;
 pub mod log_panel;
+pub mod output_panel;
+pub mod reload_panel;
+pub mod stats_panel;
 pub mod tabs;
+pub mod tag_panel;
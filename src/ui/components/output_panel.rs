@@ -0,0 +1,47 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::ui::glyphs::glyphs;
+use crate::ui::layout::centered_rect;
+
+pub fn render_output_panel(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    let Some(report) = app.failure_report.as_ref() else {
+        return;
+    };
+    let area = centered_rect(80, 70, area);
+    frame.render_widget(Clear, area);
+
+    let lines: Vec<Line> = if report.lines.is_empty() {
+        vec![Line::from(Span::styled(
+            "The command printed nothing.",
+            Style::default().fg(theme.muted),
+        ))]
+    } else {
+        report
+            .lines
+            .iter()
+            .map(|line| Line::from(Span::styled(line.clone(), Style::default().fg(theme.text))))
+            .collect()
+    };
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(inner_height) as u16;
+    app.output_scroll = app.output_scroll.min(max_scroll);
+
+    let widget = Paragraph::new(lines).scroll((app.output_scroll, 0)).block(
+        Block::default()
+            .title(format!(" {} output (Esc to close) ", report.title))
+            .borders(Borders::ALL)
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(theme.danger))
+            .style(Style::default().bg(theme.bg)),
+    );
+    frame.render_widget(widget, area);
+}
@@ -0,0 +1,64 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::ui::glyphs::glyphs;
+use crate::ui::layout::centered_rect;
+
+pub fn render_reload_panel(frame: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme;
+    let Some(diff) = app.reload_diff.as_ref() else {
+        return;
+    };
+    let area = centered_rect(60, 50, area);
+    frame.render_widget(Clear, area);
+
+    let sections = [
+        ("Added", &diff.added, theme.success),
+        ("Removed", &diff.removed, theme.danger),
+        ("Modified", &diff.modified, theme.warning),
+    ];
+    let mut lines = Vec::new();
+    for (label, ids, color) in sections {
+        if ids.is_empty() {
+            continue;
+        }
+        lines.push(Line::from(Span::styled(
+            format!("{label} ({})", ids.len()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", ids.join(", ")),
+            Style::default().fg(theme.text),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} added, {} removed, {} modified. Press any key to close.",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.modified.len()
+        ),
+        Style::default().fg(theme.muted),
+    )));
+
+    let widget = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Catalog reloaded ")
+            .borders(Borders::ALL)
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(if diff.removed.is_empty() {
+                theme.primary
+            } else {
+                theme.warning
+            }))
+            .style(Style::default().bg(theme.bg)),
+    );
+    frame.render_widget(widget, area);
+}
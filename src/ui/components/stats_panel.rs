@@ -0,0 +1,105 @@
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::ui::glyphs::glyphs;
+use crate::ui::layout::centered_rect;
+
+pub fn render_stats_panel(frame: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme;
+    let area = centered_rect(50, 70, area);
+    frame.render_widget(Clear, area);
+
+    let total = app.entries.len();
+    let installable = app.installable_count();
+    let pinned = app
+        .entries
+        .iter()
+        .filter(|entry| app.is_favorite(entry))
+        .count();
+
+    let heading = |text: String| {
+        Line::from(Span::styled(
+            text,
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    let row = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{label:<18}"), Style::default().fg(theme.muted)),
+            Span::styled(
+                value,
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+        ])
+    };
+
+    let mut lines = vec![
+        heading("Catalog".to_string()),
+        row("Apps", total.to_string()),
+        row("Installed", app.installed_ids.len().to_string()),
+        row("Conflicts", app.conflict_ids.len().to_string()),
+        row("Outdated", app.outdated.len().to_string()),
+        row("Pinned", pinned.to_string()),
+        row("Tags", app.tags.len().to_string()),
+        Line::from(""),
+        heading(format!("On {}", app.platform.label())),
+        row("Installable", format!("{installable}/{total}")),
+        row("Unavailable", (total - installable).to_string()),
+        Line::from(""),
+        heading("Categories (installed/total)".to_string()),
+    ];
+    lines.extend(
+        app.categories
+            .iter()
+            .zip(&app.category_totals)
+            .zip(&app.category_installed)
+            .map(|((category, &total), &installed)| {
+                let mut line = row(category, format!("{:<8}", format!("{installed}/{total}")));
+                let color = if total > 0 && installed == total {
+                    theme.success
+                } else {
+                    theme.primary
+                };
+                let (full, empty) = coverage_bar(installed, total);
+                line.spans
+                    .push(Span::styled(full, Style::default().fg(color)));
+                line.spans
+                    .push(Span::styled(empty, Style::default().fg(theme.muted)));
+                line
+            }),
+    );
+
+    let widget = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Stats (Esc to close) ")
+            .borders(Borders::ALL)
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(theme.primary))
+            .style(Style::default().bg(theme.bg)),
+    );
+    frame.render_widget(widget, area);
+}
+
+const BAR_WIDTH: usize = 12;
+
+/// The filled and empty halves of a category's installed/total bar. Any progress shows at
+/// least one cell, and only a complete category fills the bar.
+fn coverage_bar(installed: usize, total: usize) -> (String, String) {
+    let filled = match (installed, total) {
+        (_, 0) | (0, _) => 0,
+        _ if installed >= total => BAR_WIDTH,
+        _ => (installed * BAR_WIDTH / total).clamp(1, BAR_WIDTH - 1),
+    };
+    (
+        glyphs().bar_full.repeat(filled),
+        glyphs().bar_empty.repeat(BAR_WIDTH - filled),
+    )
+}
@@ -2,16 +2,36 @@ use ratatui::{
     layout::Rect,
     prelude::*,
     style::{Modifier, Style},
-    widgets::{Block, BorderType, Borders, Tabs},
+    widgets::{Block, Borders, Tabs},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::app::state::App;
-use crate::ui::theme::*;
+use crate::app::state::{App, TABS};
+use crate::ui::glyphs::glyphs;
 
-const TABS: [&str; 3] = ["All", "Installed", "Categories"];
+const DIVIDER: &str = " | ";
 
-pub fn render_main_tabs(frame: &mut Frame<'_>, area: Rect, app: &App) {
+/// Where each title of a bordered `Tabs` lands: one cell of padding either side, then the
+/// divider. Titles past the right edge are clipped or left out.
+fn title_areas(area: Rect, widths: impl Iterator<Item = usize>) -> Vec<Rect> {
+    let right = area.right().saturating_sub(1);
+    let mut x = area.x + 1;
+    let mut areas = Vec::new();
+    for width in widths {
+        if x >= right {
+            break;
+        }
+        let full = (width + 2) as u16;
+        areas.push(Rect::new(x, area.y + 1, full.min(right - x), 1));
+        x = x.saturating_add(full + DIVIDER.len() as u16);
+    }
+    areas
+}
+
+pub fn render_main_tabs(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    app.click_areas.tabs = title_areas(area, TABS.iter().map(|title| title.width()));
     let tab_titles = TABS
         .iter()
         .map(|title| Line::from(*title))
@@ -22,36 +42,47 @@ pub fn render_main_tabs(frame: &mut Frame<'_>, area: Rect, app: &App) {
             Block::default()
                 .title(" TUIHub ")
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(C_PANEL)),
+                .border_set(glyphs().border)
+                .border_style(Style::default().fg(theme.panel)),
         )
-        .style(Style::default().fg(C_MUTED))
+        .style(Style::default().fg(theme.muted))
         .highlight_style(
             Style::default()
-                .fg(C_PRIMARY)
+                .fg(theme.primary)
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         )
-        .divider(" | ");
+        .divider(DIVIDER);
     frame.render_widget(tabs, area);
 }
 
-pub fn render_category_tabs(frame: &mut Frame<'_>, area: Rect, app: &App) {
-    let category_titles = app
+pub fn render_category_tabs(frame: &mut Frame<'_>, area: Rect, app: &mut App) {
+    let theme = app.theme;
+    let category_titles: Vec<Line> = app
         .categories
         .iter()
-        .map(|c| Line::from(c.to_string()))
-        .collect::<Vec<_>>();
+        .enumerate()
+        .map(|(idx, c)| {
+            let total = app.category_totals.get(idx).copied().unwrap_or(0);
+            let installed = app.category_installed.get(idx).copied().unwrap_or(0);
+            Line::from(format!("{c} ({installed}/{total})"))
+                .style(Style::default().fg(theme.category_color(c)))
+        })
+        .collect();
+    app.click_areas.categories = title_areas(area, category_titles.iter().map(Line::width));
     let cat_tabs = Tabs::new(category_titles)
         .select(app.selected_category)
         .block(
             Block::default()
                 .title(" Category Filter ")
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(C_PANEL)),
+                .border_set(glyphs().border)
+                .border_style(Style::default().fg(theme.panel)),
+        )
+        .style(Style::default().fg(theme.muted))
+        // No color here, so the selected tab keeps its category color.
+        .highlight_style(
+            theme.emphasize(Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)),
         )
-        .style(Style::default().fg(C_MUTED))
-        .highlight_style(Style::default().fg(C_SUCCESS).add_modifier(Modifier::BOLD))
-        .divider(" | ");
+        .divider(DIVIDER);
     frame.render_widget(cat_tabs, area);
 }
@@ -0,0 +1,67 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    prelude::*,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::state::App;
+use crate::ui::glyphs::glyphs;
+use crate::ui::layout::centered_rect;
+
+pub fn render_tag_panel(frame: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme;
+    let area = centered_rect(40, 60, area);
+    frame.render_widget(Clear, area);
+
+    let mode = if app.tag_match_all {
+        "match all"
+    } else {
+        "match any"
+    };
+    let block = Block::default()
+        .title(format!(" Tags ({mode}) "))
+        .borders(Borders::ALL)
+        .border_set(glyphs().border)
+        .border_style(Style::default().fg(theme.primary))
+        .style(Style::default().bg(theme.bg));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = app
+        .tags
+        .iter()
+        .map(|tag| {
+            let active = app.active_tags.contains(tag);
+            let checkbox = if active { "[x]" } else { "[ ]" };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{checkbox} "), Style::default().fg(theme.primary)),
+                Span::styled(
+                    tag.clone(),
+                    Style::default().fg(if active { theme.success } else { theme.text }),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        theme.emphasize(
+            Style::default()
+                .bg(theme.highlight)
+                .fg(theme.text)
+                .add_modifier(Modifier::BOLD),
+        ),
+    );
+    let mut state = ListState::default().with_selected(Some(app.tag_cursor));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let hint = Paragraph::new("Space toggle  M all/any  C clear  Esc close")
+        .style(Style::default().fg(theme.muted));
+    frame.render_widget(hint, chunks[1]);
+}
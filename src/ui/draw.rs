@@ -2,24 +2,39 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
     style::{Color, Modifier, Style},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame, Terminal,
 };
 
-use crate::app::state::{App, ConfirmAction};
+use crate::app::state::{App, ClickAreas};
 use crate::ui::components::{
-    app_list::render_app_list, detail_panel::render_detail_panel, footer::render_footer,
-    tabs::render_main_tabs,
+    app_list::render_app_list, batch_panel::render_batch_panel,
+    confirm_dialog::render_confirm_dialog, detail_panel::render_detail_panel,
+    empty_state::render_empty_state, footer::render_footer, help_panel::render_help_panel,
+    output_panel::render_output_panel, reload_panel::render_reload_panel,
+    stats_panel::render_stats_panel, tabs::render_main_tabs, tag_panel::render_tag_panel,
 };
+use crate::ui::glyphs::glyphs;
 use crate::ui::layout::centered_rect;
-use crate::ui::theme::*;
+
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
 
 pub fn ui(frame: &mut Frame<'_>, app: &mut App) {
+    let theme = app.theme;
+    // Stale areas would turn clicks on a too-small screen into list moves.
+    app.click_areas = ClickAreas::default();
     frame.render_widget(
-        Block::default().style(Style::default().bg(C_BG)),
+        Block::default().style(Style::default().bg(theme.bg)),
         frame.area(),
     );
 
+    let size = frame.area();
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        render_too_small(frame, app);
+        return;
+    }
+
     let vertical = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -39,13 +54,53 @@ pub fn ui(frame: &mut Frame<'_>, app: &mut App) {
     }
 
     let search_title = if app.search_mode {
-        " Search mode (/): typing... Enter apply, Esc close "
+        ": Enter apply, Esc close "
     } else {
         " Search (/ to start, Esc clear) "
     };
+    let search_title = if app.active_tags.is_empty() {
+        search_title.to_string()
+    } else {
+        let joiner = if app.tag_match_all { " + " } else { " | " };
+        let tags = app
+            .active_tags
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(joiner);
+        format!("{search_title}[tags: {tags}] ")
+    };
+    let search_title = if app.search_regex_error.is_some() {
+        format!("{search_title}[invalid regex] ")
+    } else if app.search_regex.is_some() {
+        format!("{search_title}[regex] ")
+    } else {
+        search_title
+    };
+
+    // While typing, lead with the live match count so the query's reach shows per keystroke.
+    let search_title = if app.search_mode {
+        let count = match app.filtered_indices.len() {
+            0 => Span::styled(
+                "no matches",
+                Style::default()
+                    .fg(theme.danger)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            1 => Span::styled("1 match", Style::default().fg(theme.success)),
+            n => Span::styled(format!("{n} matches"), Style::default().fg(theme.success)),
+        };
+        Line::from(vec![
+            Span::raw(" Search mode — "),
+            count,
+            Span::raw(search_title),
+        ])
+    } else {
+        Line::from(search_title)
+    };
 
     let search_text = if app.search_input.is_empty() {
-        "Type to filter by name, id, category, description".to_string()
+        "Type to filter by name, id, category, tags, description (? for field syntax)".to_string()
     } else {
         app.search_input.clone()
     };
@@ -54,90 +109,63 @@ pub fn ui(frame: &mut Frame<'_>, app: &mut App) {
             Block::default()
                 .title(search_title)
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(if app.search_mode {
-                    C_PRIMARY
+                .border_set(glyphs().border)
+                .border_style(Style::default().fg(if app.search_regex_error.is_some() {
+                    theme.danger
+                } else if app.search_mode {
+                    theme.primary
                 } else {
-                    C_PANEL
+                    theme.panel
                 })),
         )
         .style(if app.search_mode {
-            Style::default().fg(C_TEXT)
+            Style::default().fg(theme.text)
         } else {
-            Style::default().fg(C_MUTED)
+            Style::default().fg(theme.muted)
         });
 
     frame.render_widget(search, vertical[2]);
 
-    let body = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
-        .split(vertical[3]);
+    if app.entries.is_empty() {
+        render_empty_state(frame, vertical[3], app);
+    } else {
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
+            .split(vertical[3]);
 
-    render_app_list(frame, body[0], app);
-    render_detail_panel(frame, body[1], app);
+        render_app_list(frame, body[0], app);
+        render_detail_panel(frame, body[1], app);
+    }
 
     render_footer(frame, vertical[4], app);
 
-    if app.confirm_mode {
-        let area = centered_rect(50, 25, frame.area());
-        frame.render_widget(Clear, area);
+    if app.tag_mode {
+        render_tag_panel(frame, frame.area(), app);
+    }
 
-        let msg = if let Some(ConfirmAction::Uninstall(ref targets)) = app.confirm_action {
-            let names = targets
-                .iter()
-                .map(|t| t.name.clone())
-                .collect::<Vec<_>>()
-                .join(", ");
-            format!("Are you sure you want to uninstall:\n{}?", names)
-        } else {
-            "Confirm action?".to_string()
-        };
+    if app.help_mode {
+        render_help_panel(frame, frame.area(), app);
+    }
 
-        let block = Paragraph::new(msg)
-            .style(Style::default().fg(C_TEXT))
-            .wrap(Wrap { trim: true })
-            .alignment(ratatui::prelude::Alignment::Center)
-            .block(
-                Block::default()
-                    .title(" Confirm Uninstall ")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(C_PANEL)),
-            );
-        frame.render_widget(block, area);
+    if app.stats_mode {
+        render_stats_panel(frame, frame.area(), app);
+    }
 
-        let btn_area = Rect::new(
-            area.x + 2,
-            area.y + area.height - 3,
-            area.x + area.width - 2,
-            area.y + area.height - 1,
-        );
+    if app.output_mode {
+        render_output_panel(frame, frame.area(), app);
+    }
 
-        let yes_style = if app.confirm_selected {
-            Style::default()
-                .fg(C_BG)
-                .bg(C_SUCCESS)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(C_SUCCESS).add_modifier(Modifier::BOLD)
-        };
-        let no_style = if !app.confirm_selected {
-            Style::default()
-                .fg(C_BG)
-                .bg(C_WARNING)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(C_WARNING).add_modifier(Modifier::BOLD)
-        };
+    if app.confirm_dialog.is_some() {
+        render_confirm_dialog(frame, frame.area(), app);
+    }
 
-        let btns = Paragraph::new(vec![Line::from(vec![
-            Span::styled("[ Yes ] ", yes_style),
-            Span::styled("[ No ] ", no_style),
-        ])
-        .alignment(ratatui::prelude::Alignment::Center)])
-        .block(Block::default().borders(Borders::NONE));
-        frame.render_widget(btns, btn_area);
+    if app.batch_report.is_some() {
+        render_batch_panel(frame, frame.area(), app);
+    }
+
+    if app.reload_diff.is_some() {
+        render_reload_panel(frame, frame.area(), app);
     }
 
     if app.search_mode {
@@ -147,6 +175,34 @@ pub fn ui(frame: &mut Frame<'_>, app: &mut App) {
     }
 }
 
+fn render_too_small(frame: &mut Frame<'_>, app: &App) {
+    let theme = app.theme;
+    let size = frame.area();
+    let msg = vec![
+        Line::from(Span::styled(
+            format!("Terminal too small (need {MIN_WIDTH}x{MIN_HEIGHT})"),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("Current size: {}x{}", size.width, size.height),
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let area = Rect {
+        y: size.y + size.height.saturating_sub(2) / 2,
+        height: size.height.min(2),
+        ..size
+    };
+    frame.render_widget(
+        Paragraph::new(msg)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
 #[allow(dead_code)]
 pub fn show_transient_message(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
@@ -162,7 +218,7 @@ pub fn show_transient_message(
                 Block::default()
                     .title(" Running External Command ")
                     .borders(Borders::ALL)
-                    .border_type(BorderType::Thick),
+                    .border_set(glyphs().thick_border),
             );
         frame.render_widget(block, area);
     })?;
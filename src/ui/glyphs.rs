@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::symbols::{border, scrollbar};
+
+/// Every non-ASCII symbol the UI draws, so `--ascii` can swap them all in one place.
+pub struct Glyphs {
+    pub border: border::Set,
+    pub thick_border: border::Set,
+    pub scrollbar: scrollbar::Set,
+    pub ellipsis: &'static str,
+    pub up_down: &'static str,
+    pub left_right: &'static str,
+    pub separator: &'static str,
+    pub favorite: &'static str,
+    pub folded: &'static str,
+    pub unfolded: &'static str,
+    pub ok: &'static str,
+    pub failed: &'static str,
+    pub bar_full: &'static str,
+    pub bar_empty: &'static str,
+}
+
+const UNICODE: Glyphs = Glyphs {
+    border: border::ROUNDED,
+    thick_border: border::THICK,
+    scrollbar: scrollbar::DOUBLE_VERTICAL,
+    ellipsis: "…",
+    up_down: "↑/↓",
+    left_right: "←/→",
+    separator: "·",
+    favorite: "★",
+    folded: "▸",
+    unfolded: "▾",
+    ok: "✓",
+    failed: "✗",
+    bar_full: "█",
+    bar_empty: "░",
+};
+
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+const ASCII: Glyphs = Glyphs {
+    border: ASCII_BORDER,
+    thick_border: ASCII_BORDER,
+    scrollbar: scrollbar::Set {
+        track: "|",
+        thumb: "#",
+        begin: "^",
+        end: "v",
+    },
+    ellipsis: "...",
+    up_down: "^/v",
+    left_right: "</>",
+    separator: "-",
+    favorite: "*",
+    folded: ">",
+    unfolded: "v",
+    ok: "+",
+    failed: "x",
+    bar_full: "#",
+    bar_empty: ".",
+};
+
+/// Chosen once at startup, before the first frame.
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_ascii(ascii: bool) {
+    ASCII_MODE.store(ascii, Ordering::Relaxed);
+}
+
+/// Terminals that are known to garble box drawing and arrows.
+pub fn ascii_terminal() -> bool {
+    std::env::var("TERM").is_ok_and(|term| {
+        matches!(
+            term.as_str(),
+            "dumb" | "vt52" | "vt100" | "vt102" | "vt220" | "ansi"
+        )
+    })
+}
+
+pub fn glyphs() -> &'static Glyphs {
+    if ASCII_MODE.load(Ordering::Relaxed) {
+        &ASCII
+    } else {
+        &UNICODE
+    }
+}
+
+impl Glyphs {
+    /// Rewrites the arrow names used in key hints, e.g. `↑/↓ j/k`.
+    pub fn keys<'a>(&self, label: &'a str) -> Cow<'a, str> {
+        if !label.contains(UNICODE.up_down) && !label.contains(UNICODE.left_right) {
+            return Cow::Borrowed(label);
+        }
+        Cow::Owned(
+            label
+                .replace(UNICODE.up_down, self.up_down)
+                .replace(UNICODE.left_right, self.left_right),
+        )
+    }
+}
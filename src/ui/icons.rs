@@ -0,0 +1,37 @@
+use crate::registry::model::AppEntry;
+
+/// Cells the icon column takes, not counting the space after it. Emoji are two cells wide.
+pub const ICON_WIDTH: usize = 2;
+
+/// Fallbacks for entries without an `icon`, matched against the lowercased category.
+const CATEGORY_ICONS: &[(&str, &str)] = &[
+    ("editor", "📝"),
+    ("git", "🌱"),
+    ("system", "📊"),
+    ("monitor", "📊"),
+    ("file", "📁"),
+    ("container", "🐳"),
+    ("docker", "🐳"),
+    ("kubernetes", "⎈"),
+    ("media", "🎵"),
+    ("music", "🎵"),
+    ("feed", "📰"),
+    ("news", "📰"),
+    ("network", "🌐"),
+    ("shell", "🐚"),
+    ("database", "🗄"),
+];
+
+const DEFAULT_ICON: &str = "📦";
+
+/// The entry's own icon, else one derived from its category.
+pub fn icon_for(entry: &AppEntry) -> &str {
+    if let Some(icon) = entry.icon.as_deref().filter(|icon| !icon.trim().is_empty()) {
+        return icon;
+    }
+    let category = entry.category.to_ascii_lowercase();
+    CATEGORY_ICONS
+        .iter()
+        .find(|(key, _)| category.contains(key))
+        .map_or(DEFAULT_ICON, |(_, icon)| icon)
+}
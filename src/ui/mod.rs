@@ -1,5 +1,6 @@
 pub mod components;
 pub mod draw;
+pub mod glyphs;
+pub mod icons;
 pub mod layout;
 pub mod theme;
-
@@ -1,9 +1,207 @@
-use ratatui::style::Color;
-
-pub const C_BG: Color = Color::Rgb(15, 20, 28);
-pub const C_PANEL: Color = Color::Rgb(28, 38, 52);
-pub const C_MUTED: Color = Color::Rgb(130, 144, 164);
-pub const C_TEXT: Color = Color::Rgb(226, 234, 244);
-pub const C_PRIMARY: Color = Color::Rgb(111, 201, 255);
-pub const C_SUCCESS: Color = Color::Rgb(112, 220, 142);
-pub const C_WARNING: Color = Color::Rgb(255, 210, 110);
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::system::paths::config_dir;
+use crate::utils::stable_hash;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub bg: Color,
+    pub panel: Color,
+    pub muted: Color,
+    pub text: Color,
+    pub primary: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub highlight: Color,
+    pub monochrome: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            bg: Color::Rgb(15, 20, 28),
+            panel: Color::Rgb(28, 38, 52),
+            muted: Color::Rgb(130, 144, 164),
+            text: Color::Rgb(226, 234, 244),
+            primary: Color::Rgb(111, 201, 255),
+            success: Color::Rgb(112, 220, 142),
+            warning: Color::Rgb(255, 210, 110),
+            danger: Color::Rgb(255, 118, 118),
+            highlight: Color::Rgb(32, 57, 84),
+            monochrome: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 3] = [Self::Dark, Self::Light, Self::HighContrast];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "dark",
+            ThemePreset::Light => "light",
+            ThemePreset::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|preset| preset.name() == name)
+    }
+
+    pub fn next(&self) -> Self {
+        let idx = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self {
+            ThemePreset::Dark => Theme::default(),
+            ThemePreset::Light => Theme {
+                bg: Color::Rgb(250, 250, 250),
+                panel: Color::Rgb(196, 202, 212),
+                muted: Color::Rgb(88, 98, 112),
+                text: Color::Rgb(20, 24, 32),
+                primary: Color::Rgb(0, 95, 175),
+                success: Color::Rgb(20, 128, 60),
+                warning: Color::Rgb(172, 102, 0),
+                danger: Color::Rgb(190, 30, 30),
+                highlight: Color::Rgb(205, 225, 245),
+                monochrome: false,
+            },
+            // Pure black/white with saturated accents; no mid-grays anywhere.
+            ThemePreset::HighContrast => Theme {
+                bg: Color::Black,
+                panel: Color::White,
+                muted: Color::White,
+                text: Color::White,
+                primary: Color::LightCyan,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                danger: Color::LightRed,
+                highlight: Color::Blue,
+                monochrome: false,
+            },
+        }
+    }
+}
+
+impl Theme {
+    /// Default terminal colors only; emphasis comes from modifiers instead of color.
+    pub fn monochrome() -> Self {
+        Self {
+            bg: Color::Reset,
+            panel: Color::Reset,
+            muted: Color::Reset,
+            text: Color::Reset,
+            primary: Color::Reset,
+            success: Color::Reset,
+            warning: Color::Reset,
+            danger: Color::Reset,
+            highlight: Color::Reset,
+            monochrome: true,
+        }
+    }
+
+    /// A fixed accent per category, picked by hashing its name so it never changes between
+    /// sessions. Case-insensitive, like category matching.
+    pub fn category_color(&self, category: &str) -> Color {
+        if self.monochrome {
+            return self.text;
+        }
+        let palette = [self.primary, self.success, self.warning, self.danger];
+        let hash = stable_hash(&category.to_ascii_lowercase());
+        palette[(hash % palette.len() as u64) as usize]
+    }
+
+    pub fn no_color_requested() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+    }
+
+    /// Marks a style as selected/active. Monochrome themes can't rely on a background color
+    /// for that, so they reverse video instead.
+    pub fn emphasize(&self, style: Style) -> Style {
+        if self.monochrome {
+            style.add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            style
+        }
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("theme.toml"))
+    }
+
+    /// Loads `theme.toml` over the given preset. Returns the theme plus any warnings about
+    /// keys or values that were ignored.
+    pub fn load(preset: ThemePreset) -> (Self, Vec<String>) {
+        let Some(path) = Self::path() else {
+            return (preset.theme(), Vec::new());
+        };
+        let Ok(raw) = fs::read_to_string(&path) else {
+            return (preset.theme(), Vec::new());
+        };
+        Self::from_toml(preset.theme(), &raw)
+    }
+
+    pub fn from_toml(base: Theme, raw: &str) -> (Self, Vec<String>) {
+        let mut theme = base;
+        let mut warnings = Vec::new();
+
+        let values: BTreeMap<String, String> = match toml::from_str(raw) {
+            Ok(values) => values,
+            Err(e) => {
+                warnings.push(format!("theme.toml ignored: {}", e.message()));
+                return (theme, warnings);
+            }
+        };
+
+        for (key, value) in values {
+            let slot = match key.as_str() {
+                "bg" => &mut theme.bg,
+                "panel" => &mut theme.panel,
+                "muted" => &mut theme.muted,
+                "text" => &mut theme.text,
+                "primary" => &mut theme.primary,
+                "success" => &mut theme.success,
+                "warning" => &mut theme.warning,
+                "danger" => &mut theme.danger,
+                "highlight" => &mut theme.highlight,
+                _ => {
+                    warnings.push(format!("theme.toml: unknown color '{key}'"));
+                    continue;
+                }
+            };
+            match parse_hex_color(&value) {
+                Some(color) => *slot = color,
+                None => warnings.push(format!(
+                    "theme.toml: invalid hex '{value}' for '{key}', using default"
+                )),
+            }
+        }
+
+        (theme, warnings)
+    }
+}
+
+pub fn parse_hex_color(input: &str) -> Option<Color> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).ok();
+    Some(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+}
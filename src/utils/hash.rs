@@ -0,0 +1,7 @@
+/// FNV-1a. Unlike `DefaultHasher` it is fixed across Rust versions, so anything derived from
+/// it (cache file names, colors) stays the same between builds.
+pub fn stable_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -1,3 +1,14 @@
+pub mod glob;
+pub mod hash;
 pub mod search;
+pub mod time;
+pub mod version;
 
-pub use search::truncate_with_ellipsis;
+pub use glob::{glob_match, is_glob};
+pub use hash::stable_hash;
+pub use search::{
+    find_match_range, pad_to_width, truncate_at_word, truncate_with_ellipsis, SearchField,
+    SearchQuery,
+};
+pub use time::{format_elapsed, humanize_since};
+pub use version::{extract_version, is_outdated};
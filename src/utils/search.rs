@@ -1,17 +1,202 @@
-pub fn truncate_with_ellipsis(input: &str, max_chars: usize) -> String {
-    if max_chars == 0 {
-        return String::new();
-    }
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-    let chars: Vec<char> = input.chars().collect();
-    if chars.len() <= max_chars {
+/// Number of leading chars of `chars` that fit in `max_width` terminal cells.
+fn fitting_prefix(chars: &[char], max_width: usize) -> usize {
+    let mut used = 0;
+    chars
+        .iter()
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= max_width
+        })
+        .count()
+}
+
+/// Cuts `input` to `max_width` cells, ending in `ellipsis` (`…`, or `...` in ASCII mode).
+pub fn truncate_with_ellipsis(input: &str, max_width: usize, ellipsis: &str) -> String {
+    if input.width() <= max_width {
         return input.to_string();
     }
-    if max_chars == 1 {
-        return ".".to_string();
+    let reserved = ellipsis.width();
+    if max_width <= reserved {
+        return ".".repeat(max_width);
     }
 
-    let mut out = chars[..max_chars - 1].iter().collect::<String>();
-    out.push('…');
+    let chars: Vec<char> = input.chars().collect();
+    let mut out = chars[..fitting_prefix(&chars, max_width - reserved)]
+        .iter()
+        .collect::<String>();
+    out.push_str(ellipsis);
     out
 }
+
+/// Like `truncate_with_ellipsis`, but backs up to the last whitespace when it is close to
+/// the limit so words aren't cut in half.
+pub fn truncate_at_word(input: &str, max_width: usize, ellipsis: &str) -> String {
+    let reserved = ellipsis.width();
+    if input.width() <= max_width || max_width <= reserved {
+        return truncate_with_ellipsis(input, max_width, ellipsis);
+    }
+
+    let chars: Vec<char> = input.chars().collect();
+    let cut = fitting_prefix(&chars, max_width - reserved);
+    let window = ((max_width - reserved) / 2).clamp(1, 15);
+    // Include the char right after the cut so a space exactly at the limit counts.
+    let break_at = chars[..(cut + 1).min(chars.len())]
+        .iter()
+        .rposition(|c| c.is_whitespace())
+        .filter(|&idx| idx > 0 && chars[idx..cut].iter().collect::<String>().width() <= window);
+
+    match break_at {
+        Some(idx) => {
+            let mut out = chars[..idx]
+                .iter()
+                .collect::<String>()
+                .trim_end()
+                .to_string();
+            out.push_str(ellipsis);
+            out
+        }
+        None => truncate_with_ellipsis(input, max_width, ellipsis),
+    }
+}
+
+/// Left-aligns `input` in a column `width` cells wide, measuring display width rather than
+/// chars so wide (CJK, emoji) glyphs don't push later columns out of line.
+pub fn pad_to_width(input: &str, width: usize) -> String {
+    let padding = width.saturating_sub(input.width());
+    format!("{input}{}", " ".repeat(padding))
+}
+
+pub fn find_match_range(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+    let needle = needle.trim();
+    if needle.is_empty() {
+        return None;
+    }
+    let start = haystack
+        .to_ascii_lowercase()
+        .find(&needle.to_ascii_lowercase())?;
+    Some((start, start + needle.len()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Name,
+    Id,
+    Category,
+    Description,
+    Tag,
+}
+
+impl SearchField {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix.to_ascii_lowercase().as_str() {
+            "name" => Some(Self::Name),
+            "id" => Some(Self::Id),
+            "cat" | "category" => Some(Self::Category),
+            "desc" | "description" => Some(Self::Description),
+            "tag" | "tags" => Some(Self::Tag),
+            _ => None,
+        }
+    }
+}
+
+/// A search box query split into `field:value` filters and the remaining free text.
+/// Without any field prefixes the whole input is free text, exactly as typed.
+#[derive(Debug, Default)]
+pub struct SearchQuery {
+    pub free_text: String,
+    pub field_terms: Vec<(SearchField, String)>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut field_terms = Vec::new();
+        let mut free_words = Vec::new();
+        for word in input.split_whitespace() {
+            let field_term = word.split_once(':').and_then(|(prefix, value)| {
+                SearchField::from_prefix(prefix).map(|field| (field, value.to_ascii_lowercase()))
+            });
+            match field_term {
+                Some((_, value)) if value.is_empty() => {}
+                Some(term) => field_terms.push(term),
+                None => free_words.push(word),
+            }
+        }
+
+        let free_text = if field_terms.is_empty() {
+            input.to_ascii_lowercase()
+        } else {
+            free_words.join(" ").to_ascii_lowercase()
+        };
+        Self {
+            free_text,
+            field_terms,
+        }
+    }
+
+    pub fn has_free_text(&self) -> bool {
+        !self.free_text.trim().is_empty()
+    }
+
+    /// The term to highlight inside `field`: its own filter if present, else the free text.
+    pub fn highlight_term(&self, field: SearchField) -> &str {
+        self.field_terms
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or(&self.free_text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_with_ellipsis_counts_wide_glyphs_as_two_cells() {
+        assert_eq!(truncate_with_ellipsis("日本語テキスト", 7, "…"), "日本語…");
+        // A wide glyph that would straddle the limit is dropped, not split.
+        assert_eq!(truncate_with_ellipsis("日本語テキスト", 6, "…"), "日本…");
+        assert_eq!(truncate_with_ellipsis("🚀🚀🚀", 4, "…"), "🚀…");
+        assert_eq!(truncate_with_ellipsis("🚀🚀", 4, "…"), "🚀🚀");
+        assert_eq!(truncate_with_ellipsis("日本語", 2, "..."), "..");
+    }
+
+    #[test]
+    fn pad_to_width_pads_by_display_width() {
+        assert_eq!(pad_to_width("fd", 5), "fd   ");
+        assert_eq!(pad_to_width("日本", 5), "日本 ");
+        assert_eq!(pad_to_width("🚀x", 5), "🚀x  ");
+        assert_eq!(pad_to_width("日本語", 4), "日本語");
+    }
+
+    #[test]
+    fn truncate_at_word_backs_up_to_a_space() {
+        assert_eq!(
+            truncate_at_word("a fast file finder", 12, "…"),
+            "a fast file…"
+        );
+        assert_eq!(truncate_at_word("short", 12, "…"), "short");
+    }
+
+    #[test]
+    fn truncate_at_word_without_spaces_cuts_mid_word() {
+        assert_eq!(truncate_at_word("supercalifragilistic", 8, "…"), "superca…");
+        assert_eq!(
+            truncate_at_word("supercalifragilistic", 8, "..."),
+            "super..."
+        );
+    }
+
+    #[test]
+    fn truncate_at_word_measures_multi_byte_text_in_cells() {
+        assert_eq!(
+            truncate_at_word("café crème brûlée", 12, "…"),
+            "café crème…"
+        );
+        // Each CJK glyph is two cells wide.
+        assert_eq!(truncate_at_word("日本語 テキスト", 10, "…"), "日本語…");
+        assert_eq!(truncate_at_word("日本語テキスト", 9, "…"), "日本語テ…");
+    }
+}
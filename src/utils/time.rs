@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Compact relative time such as "5m ago" or "3d ago".
+pub fn humanize_since(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - then).num_seconds();
+    if secs < 60 {
+        return "just now".to_string();
+    }
+    let (value, unit) = match secs {
+        s if s < 3_600 => (s / 60, "m"),
+        s if s < 86_400 => (s / 3_600, "h"),
+        s if s < 30 * 86_400 => (s / 86_400, "d"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "mo"),
+        s => (s / (365 * 86_400), "y"),
+    };
+    format!("{value}{unit} ago")
+}
+
+/// Short elapsed time for status messages: "12.3s", or "2m 05s" past a minute.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
@@ -0,0 +1,56 @@
+use std::cmp::Ordering;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// The first version-looking token in a command's output, e.g. "1.4.0" from
+/// "btop version: 1.4.0". A leading `v` is dropped.
+pub fn extract_version(output: &str) -> Option<String> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"\d+(?:\.\d+)+(?:-[0-9A-Za-z.]+)?").expect("version pattern is valid")
+    });
+    pattern.find(output).map(|m| m.as_str().to_string())
+}
+
+/// Semver-style comparison that tolerates anything: missing components count as 0,
+/// a pre-release (`1.2.0-rc1`) sorts before its release, and non-numeric parts fall back
+/// to plain string order.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_core, a_pre) = split_pre(a);
+    let (b_core, b_pre) = split_pre(b);
+
+    let a_parts: Vec<&str> = a_core.split('.').collect();
+    let b_parts: Vec<&str> = b_core.split('.').collect();
+    for i in 0..a_parts.len().max(b_parts.len()) {
+        let a_part = a_parts.get(i).copied().unwrap_or("0");
+        let b_part = b_parts.get(i).copied().unwrap_or("0");
+        let ordering = match (a_part.parse::<u64>(), b_part.parse::<u64>()) {
+            (Ok(a_num), Ok(b_num)) => a_num.cmp(&b_num),
+            _ => a_part.cmp(b_part),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(a_pre), Some(b_pre)) => a_pre.cmp(b_pre),
+    }
+}
+
+pub fn is_outdated(installed: &str, latest: &str) -> bool {
+    compare_versions(installed, latest) == Ordering::Less
+}
+
+fn split_pre(version: &str) -> (&str, Option<&str>) {
+    let version = version.trim().trim_start_matches(['v', 'V']);
+    let version = version.split('+').next().unwrap_or(version);
+    match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    }
+}